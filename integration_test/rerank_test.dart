@@ -0,0 +1,75 @@
+import 'package:flutter_test/flutter_test.dart';
+import 'package:offline_engine/main.dart';
+import 'package:offline_engine/searchtextfunction.dart';
+import 'package:offline_engine/src/rust/api/rerank.dart';
+import 'package:offline_engine/src/rust/frb_generated.dart';
+import 'package:integration_test/integration_test.dart';
+
+// Stands in for the Rust side: scores candidates by their length, keeps their
+// order without scores like rerank does when no reranker is loaded, or fails
+class FakeRerankApi implements RustLibApi {
+  bool loaded = true;
+  bool fail = false;
+  List<String> candidatesSeen = [];
+
+  @override
+  List<RerankedCandidate> crateApiRerankRerank(
+      {required String query,
+      required List<String> candidates,
+      required int? budgetMs,
+      required int? topN}) {
+    if (fail) {
+      throw Exception('reranker failed');
+    }
+    candidatesSeen = candidates;
+    if (!loaded) {
+      return List.generate(candidates.length, (i) => RerankedCandidate(index: i, score: null));
+    }
+    final order = List.generate(candidates.length, (i) => i)
+      ..sort((a, b) => candidates[b].length.compareTo(candidates[a].length));
+    return order
+        .map((i) => RerankedCandidate(index: i, score: candidates[i].length.toDouble()))
+        .toList();
+  }
+
+  @override
+  dynamic noSuchMethod(Invocation invocation) => super.noSuchMethod(invocation);
+}
+
+void main() {
+  IntegrationTestWidgetsFlutterBinding.ensureInitialized();
+  final api = FakeRerankApi();
+  setUpAll(() => RustLib.initMock(api: api));
+
+  List<SearchResult> vectorResults() => [
+        SearchResult(DocumentSection(content: 'short'), 0.9),
+        SearchResult(DocumentSection(content: 'the longest section'), 0.8),
+        SearchResult(DocumentSection(content: 'medium one'), 0.7),
+      ];
+
+  setUp(() {
+    api.loaded = true;
+    api.fail = false;
+  });
+
+  test('Orders search results by the reranker', () async {
+    final results = vectorResults();
+    final reranked = await rerankResults('query', results);
+    expect(api.candidatesSeen, ['short', 'the longest section', 'medium one']);
+    expect(reranked, [results[1], results[2], results[0]]);
+  });
+
+  test('Keeps the vector order without a reranker', () async {
+    api.loaded = false;
+    final results = vectorResults();
+    final reranked = await rerankResults('query', results);
+    expect(reranked, results);
+  });
+
+  test('Keeps the vector order when reranking fails', () async {
+    api.fail = true;
+    final results = vectorResults();
+    final reranked = await rerankResults('query', results);
+    expect(reranked, results);
+  });
+}
@@ -36,7 +36,8 @@ import 'package:offline_engine/src/rust/frb_generated.dart';
 //import 'package:offline_engine/src/rust/api/tokenizer.dart' as tokenizer_api;
 import 'package:offline_engine/src/rust/api/pdf_text_extractor.dart' as pdf_api;
 import 'package:offline_engine/src/rust/api/text_splitter.dart' as text_splitter;
-import 'package:offline_engine/src/rust/api/rustpotion.dart' as rustpotion;
+import 'package:offline_engine/src/rust/api/embedding.dart' as embedding_api;
+import 'package:offline_engine/src/rust/api/rerank.dart' as rerank_api;
 //import 'onnxruntime_functions.dart';
 //import 'package:offline_engine/src/rust/api/fast_embed.dart' as fast_embed; Don't delete, works on Android but needs .so for onnx runtime for android
 //import 'package:offline_engine/src/rust/api/ort_functions.dart' as ort_api;
@@ -60,6 +61,12 @@ import 'handlers/database_handler.dart';
 //const String MODEL_RELATIVE_PATH = 'assets/pretrainedMiniLM-L6-v2/model_qint8_arm64.onnx';
 //const String TOKENIZER_RELATIVE_PATH = 'assets/pretrainedMiniLM-L6-v2/tokenizer_MiniLM-L6-v2.json';
 
+// Id of the embedding model used for indexing and search, also the name of its directory under models/
+const String EMBEDDING_MODEL_ID = 'RETRIEVAL32M';
+
+// Disk space for embeddings kept between imports, 0 turns the cache off
+const int EMBEDDING_CACHE_MB = 256;
+
 // Add this near the top of the file, after other class declarations
 class ProStatus {
   static bool isPro = false;  // Default value, can be changed later
@@ -297,8 +304,22 @@ Future<void> main() async {
   // Copy assets if needed
   await copyAssetsToAppDir(appDir2.path);
   
-  // Initialize RustPotion
-  rustpotion.initPotion(appDir: appDir2.path);
+  // Initialize the embedding model
+  embedding_api.initEmbeddingModel(modelId: EMBEDDING_MODEL_ID, modelPath: path.join(appDir2.path, 'models', EMBEDDING_MODEL_ID));
+
+  // Reuse embeddings of unchanged sections when documents are imported again
+  try {
+    embedding_api.initEmbeddingCache(cacheDir: path.join(appDir2.path, 'embedding_cache'), maxMegabytes: EMBEDDING_CACHE_MB);
+  } catch (e) {
+    debugPrint("Error opening embedding cache: $e");
+  }
+
+  // Load the search result reranker if its model is installed
+  try {
+    rerank_api.initReranker(modelPath: path.join(appDir2.path, 'models', 'reranker'));
+  } catch (e) {
+    debugPrint("Error loading reranker: $e");
+  }
 
   // Print database size using static method
   final sizeInBytes = Store.dbFileSize(dbPath);  // Use static method
@@ -1516,7 +1537,7 @@ List<double> normalizeEmbedding(List<double> embedding) {
 
 Future<List<double>> getEmbedding(String text) async {
   //return await ort_api.ortTokenizeAndInfer(text: text);
-  return await rustpotion.getEmbeddingFromRustpotion(text: text);
+  return await embedding_api.getEmbedding(modelId: EMBEDDING_MODEL_ID, text: text, role: embedding_api.EmbeddingRole.query);
 }
 
 double cosineSimilarity(List<double> a, List<double> b) {
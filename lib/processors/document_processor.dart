@@ -132,6 +132,8 @@ Future<void> processDocumentFile(BuildContext context, {
       filename: filename,
       fileBytes: fileBytes,
       fileType: fileType,
+      modelId: EMBEDDING_MODEL_ID,
+      modelPath: '${appDir.path}/models/$EMBEDDING_MODEL_ID',
       rootIsolateToken: RootIsolateToken.instance!,
       sendPort: receivePort.sendPort,
     );
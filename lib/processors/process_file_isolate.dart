@@ -11,7 +11,7 @@ import '../main.dart';  // For Document, DocumentSection, and ProcessFileArgs
 import 'package:offline_engine/src/rust/frb_generated.dart';
 import 'package:offline_engine/src/rust/api/pdf_text_extractor.dart' as pdf_api;
 import 'package:offline_engine/src/rust/api/text_splitter.dart' as text_splitter;
-import 'package:offline_engine/src/rust/api/rustpotion.dart' as rustpotion;
+import 'package:offline_engine/src/rust/api/embedding.dart' as embedding_api;
 import 'package:offline_engine/src/rust/api/fast_html2md_functions.dart' as html2md;
 import '../handlers/database_handler.dart';
 
@@ -25,6 +25,8 @@ class ProcessFileArgs {
   final String filename;
   final List<int> fileBytes;
   final String fileType;
+  final String modelId;
+  final String modelPath;
   final RootIsolateToken rootIsolateToken;
   final SendPort sendPort;
 
@@ -36,6 +38,8 @@ class ProcessFileArgs {
     required this.filename,
     required this.fileBytes,
     required this.fileType,
+    required this.modelId,
+    required this.modelPath,
     required this.rootIsolateToken,
     required this.sendPort,
   });
@@ -51,6 +55,9 @@ Future<String?> processFileInIsolate(ProcessFileArgs args) async {
     
     BackgroundIsolateBinaryMessenger.ensureInitialized(args.rootIsolateToken);
     await RustLib.init();
+    if (!embedding_api.loadedEmbeddingModels().contains(args.modelId)) {
+      embedding_api.initEmbeddingModel(modelId: args.modelId, modelPath: args.modelPath);
+    }
     
     void updateStatus(String status) {
       if (args.totalFiles > 1) {
@@ -154,7 +161,8 @@ Future<String?> processFileInIsolate(ProcessFileArgs args) async {
           }
 
           updateStatus('Computing embeddings for all sections...');
-          final embeddings = await rustpotion.getEmbeddingsFromRustpotion(texts: sections);
+          final embeddings = await embedding_api.getEmbeddings(modelId: args.modelId, texts: sections, role: embedding_api.EmbeddingRole.document);
+          logEmbeddingCacheStats();
 
           if (embeddings.isEmpty) {
             return 'No embeddings could be generated from "${args.filename}"';
@@ -225,7 +233,8 @@ Future<String?> processFileInIsolate(ProcessFileArgs args) async {
         }
 
         updateStatus('Computing embeddings for all sections...');
-        final embeddings = await rustpotion.getEmbeddingsFromRustpotion(texts: allSections);
+        final embeddings = await embedding_api.getEmbeddings(modelId: args.modelId, texts: allSections, role: embedding_api.EmbeddingRole.document);
+        logEmbeddingCacheStats();
         
         if (embeddings.isEmpty) {
           return 'No embeddings could be generated from "${args.filename}"';
@@ -279,7 +288,8 @@ Future<String?> processFileInIsolate(ProcessFileArgs args) async {
         }
 
         updateStatus('Computing embeddings for all sections...');
-        final embeddings = await rustpotion.getEmbeddingsFromRustpotion(texts: sections);
+        final embeddings = await embedding_api.getEmbeddings(modelId: args.modelId, texts: sections, role: embedding_api.EmbeddingRole.document);
+        logEmbeddingCacheStats();
 
         if (embeddings.isEmpty) {
           return 'No embeddings could be generated from "${args.filename}"';
@@ -331,4 +341,11 @@ Future<String?> processFileInIsolate(ProcessFileArgs args) async {
     debugPrint('Stack trace: $stackTrace');
     return e.toString();
   }
-}
\ No newline at end of file
+}
+
+// Shows how many sections the embedding cache saved from being embedded again
+void logEmbeddingCacheStats() {
+  final stats = embedding_api.embeddingCacheStats();
+  debugPrint('Embedding cache: ${stats.hits} hits, ${stats.misses} misses, '
+      '${stats.entries} embeddings in ${(stats.bytes / (1024 * 1024)).toStringAsFixed(1)} MB');
+}
@@ -2,11 +2,15 @@ import 'dart:io';
 import 'package:flutter/material.dart';
 import 'package:objectbox/objectbox.dart';
 import 'dart:math' show min;
-import 'package:offline_engine/src/rust/api/rustpotion.dart' as rustpotion;
+import 'package:offline_engine/src/rust/api/embedding.dart' as embedding_api;
+import 'package:offline_engine/src/rust/api/rerank.dart' as rerank_api;
 
 import 'main.dart';
 import 'objectbox.g.dart';
 
+// Time the reranker may take per search before the rest keeps the vector order
+const int RERANK_BUDGET_MS = 300;
+
 Future<List<SearchResult>> performSearch(
   String searchText,
   BuildContext context,
@@ -63,7 +67,7 @@ Future<List<SearchResult>> performSearch(
           .toList();
 
       results.sort((a, b) => b.similarity.compareTo(a.similarity));
-      return results;
+      return rerankResults(semanticSearchText, results);
     } 
     // CASE 2: Hybrid search (exact phrases provided)
     else {
@@ -119,7 +123,8 @@ Future<List<SearchResult>> performSearch(
       }).whereType<SearchResult>().toList();
 
       results.sort((a, b) => b.similarity.compareTo(a.similarity));
-      return results.take(10).toList();
+      final reranked = await rerankResults(semanticSearchText, results);
+      return reranked.take(10).toList();
     }
   } catch (e, stackTrace) {
     debugPrint("Error during search: $e");
@@ -139,6 +144,22 @@ Future<List<SearchResult>> performSearch(
   }
 }
 
+// Reorders results with the reranker; they keep their order if none is loaded
+Future<List<SearchResult>> rerankResults(String query, List<SearchResult> results) async {
+  try {
+    final reranked = rerank_api.rerank(
+      query: query,
+      candidates: results.map((result) => result.section.content).toList(),
+      budgetMs: RERANK_BUDGET_MS,
+      topN: null,
+    );
+    return reranked.map((candidate) => results[candidate.index]).toList();
+  } catch (e) {
+    debugPrint("Reranking failed, keeping vector order: $e");
+    return results;
+  }
+}
+
 Future<List<double>> getEmbedding(String text) async {
-  return await rustpotion.getEmbeddingFromRustpotion(text: text);
+  return await embedding_api.getEmbedding(modelId: EMBEDDING_MODEL_ID, text: text, role: embedding_api.EmbeddingRole.query);
 }
\ No newline at end of file
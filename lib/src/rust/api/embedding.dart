@@ -0,0 +1,107 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.7.0.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import '../frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+
+// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `clone`, `eq`, `fmt`
+
+/// Loads the model in `model_path` as `model_id` with the engine its files are
+/// for: a model2vec directory (model.safetensors, tokenizer.json) runs on
+/// RustPotion, a sentence-transformer directory (model.onnx, tokenizer.json) on
+/// ONNX Runtime when the app is built with the onnx feature.
+bool initEmbeddingModel({required String modelId, required String modelPath}) =>
+    RustLib.instance.api.crateApiEmbeddingInitEmbeddingModel(
+        modelId: modelId, modelPath: modelPath);
+
+/// Loads `model_path` and puts it in place of whatever `model_id` was, even
+/// if it needs a different engine. The old model keeps serving calls already running.
+bool swapEmbeddingModel({required String modelId, required String modelPath}) =>
+    RustLib.instance.api.crateApiEmbeddingSwapEmbeddingModel(
+        modelId: modelId, modelPath: modelPath);
+
+/// Drops `model_id`, false if it wasn't loaded
+bool unloadEmbeddingModel({required String modelId}) => RustLib.instance.api
+    .crateApiEmbeddingUnloadEmbeddingModel(modelId: modelId);
+
+/// Ids of the models that are currently loaded, whatever engine runs them
+List<String> loadedEmbeddingModels() =>
+    RustLib.instance.api.crateApiEmbeddingLoadedEmbeddingModels();
+
+/// Length of the embeddings `model_id` returns
+int embeddingDimensions({required String modelId}) =>
+    RustLib.instance.api.crateApiEmbeddingEmbeddingDimensions(modelId: modelId);
+
+/// Embeds `text` as a search query or as a document to index, see `EmbeddingRole`,
+/// reusing what the embedding cache holds for it like `get_embeddings`
+Float64List getEmbedding(
+        {required String modelId,
+        required String text,
+        required EmbeddingRole role}) =>
+    RustLib.instance.api.crateApiEmbeddingGetEmbedding(
+        modelId: modelId, text: text, role: role);
+
+/// Embeds `texts` as `role`, reusing what the embedding cache holds for them
+/// once `init_embedding_cache` opened it
+List<Float64List> getEmbeddings(
+        {required String modelId,
+        required List<String> texts,
+        required EmbeddingRole role}) =>
+    RustLib.instance.api.crateApiEmbeddingGetEmbeddings(
+        modelId: modelId, texts: texts, role: role);
+
+/// Keeps the embeddings `get_embedding(s)` computes in `cache_dir`, at most
+/// `max_megabytes` of them, so re-embedding an unchanged text reads it back.
+/// Least recently used embeddings go first. 0 megabytes turns the cache off.
+/// Model ids have to change when their model does (`swap_embedding_model`
+/// drops what the old one cached).
+bool initEmbeddingCache(
+        {required String cacheDir, required int maxMegabytes}) =>
+    RustLib.instance.api.crateApiEmbeddingInitEmbeddingCache(
+        cacheDir: cacheDir, maxMegabytes: maxMegabytes);
+
+/// Hits, misses and size of the embedding cache, all 0 when it is off
+EmbeddingCacheStats embeddingCacheStats() =>
+    RustLib.instance.api.crateApiEmbeddingEmbeddingCacheStats();
+
+/// Deletes every cached embedding
+bool clearEmbeddingCache() =>
+    RustLib.instance.api.crateApiEmbeddingClearEmbeddingCache();
+
+class EmbeddingCacheStats {
+  final PlatformInt64 hits;
+  final PlatformInt64 misses;
+  final PlatformInt64 entries;
+  final PlatformInt64 bytes;
+
+  const EmbeddingCacheStats({
+    required this.hits,
+    required this.misses,
+    required this.entries,
+    required this.bytes,
+  });
+
+  @override
+  int get hashCode =>
+      hits.hashCode ^ misses.hashCode ^ entries.hashCode ^ bytes.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is EmbeddingCacheStats &&
+          runtimeType == other.runtimeType &&
+          hits == other.hits &&
+          misses == other.misses &&
+          entries == other.entries &&
+          bytes == other.bytes;
+}
+
+/// What a text is embedded as. Retrieval models such as bge and e5 prefix
+/// queries and documents differently, and some project queries on their own.
+enum EmbeddingRole {
+  query,
+  document,
+  ;
+}
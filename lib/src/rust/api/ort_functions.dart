@@ -6,20 +6,15 @@
 import '../frb_generated.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
 
-// These functions are ignored because they are not marked as `pub`: `mean_pooling`
-
-bool initModel({required String modelPath}) =>
-    RustLib.instance.api.crateApiOrtFunctionsInitModel(modelPath: modelPath);
-
-bool testModelLoad({required String appDir}) =>
-    RustLib.instance.api.crateApiOrtFunctionsTestModelLoad(appDir: appDir);
-
-Float32List testModelInference({required String appDir}) =>
-    RustLib.instance.api.crateApiOrtFunctionsTestModelInference(appDir: appDir);
-
-Float32List ortTokenizeAndInfer({required String text}) =>
-    RustLib.instance.api.crateApiOrtFunctionsOrtTokenizeAndInfer(text: text);
-
-List<Float32List> ortTokenizeAndInferMany({required List<String> texts}) =>
+/// Loads ONNX Runtime from `library_path`, e.g. a libonnxruntime.so that isn't
+/// bundled with the app. Otherwise the first ONNX model loads the bundled one:
+/// lib/ beside the executable on Linux, the APK's native libraries on Android.
+bool initOnnxRuntime({required String libraryPath}) => RustLib.instance.api
+    .crateApiOrtFunctionsInitOnnxRuntime(libraryPath: libraryPath);
+
+/// Loads the sentence-transformer `model_path` (e.g. model_MiniLM-L6-v2.onnx)
+/// as `model_id`, using the tokenizer loaded by `init_tokenizer`. Its texts are
+/// embedded with `get_embedding`/`get_embeddings` like any other model's.
+bool initModel({required String modelId, required String modelPath}) =>
     RustLib.instance.api
-        .crateApiOrtFunctionsOrtTokenizeAndInferMany(texts: texts);
+        .crateApiOrtFunctionsInitModel(modelId: modelId, modelPath: modelPath);
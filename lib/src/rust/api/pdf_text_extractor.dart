@@ -24,6 +24,123 @@ List<TextWithLocation> extractTextFromPdfExtractMultithreaded(
         .crateApiPdfTextExtractorExtractTextFromPdfExtractMultithreaded(
             pdfBytes: pdfBytes);
 
+PdfExtractionReport extractTextFromPdfWithReport(
+        {required List<int> pdfBytes}) =>
+    RustLib.instance.api.crateApiPdfTextExtractorExtractTextFromPdfWithReport(
+        pdfBytes: pdfBytes);
+
+/// Words of one page grouped into blocks and lines, as the JSON produced by
+/// `pdf_extract::LayoutPage::to_json`. Boxes are in PDF points with the origin
+/// at the top left of the displayed page so the viewer only has to scale them.
+String extractPageLayoutJson(
+        {required List<int> pdfBytes, required int pageNumber}) =>
+    RustLib.instance.api.crateApiPdfTextExtractorExtractPageLayoutJson(
+        pdfBytes: pdfBytes, pageNumber: pageNumber);
+
+/// Finds where a search hit appears on a page. Each entry of `needles` is matched
+/// on its own, so pass the whole snippet for a phrase hit or the individual query
+/// terms for a keyword hit. Case, whitespace, punctuation and hyphenation are ignored.
+PageHighlights findTextHighlights(
+        {required List<int> pdfBytes,
+        required int pageNumber,
+        required List<String> needles}) =>
+    RustLib.instance.api.crateApiPdfTextExtractorFindTextHighlights(
+        pdfBytes: pdfBytes, pageNumber: pageNumber, needles: needles);
+
+class HighlightRect {
+  final double left;
+  final double top;
+  final double right;
+  final double bottom;
+
+  const HighlightRect({
+    required this.left,
+    required this.top,
+    required this.right,
+    required this.bottom,
+  });
+
+  @override
+  int get hashCode =>
+      left.hashCode ^ top.hashCode ^ right.hashCode ^ bottom.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is HighlightRect &&
+          runtimeType == other.runtimeType &&
+          left == other.left &&
+          top == other.top &&
+          right == other.right &&
+          bottom == other.bottom;
+}
+
+class PageHighlights {
+  final int pageNumber;
+  final double pageWidth;
+  final double pageHeight;
+  final List<HighlightRect> rects;
+
+  const PageHighlights({
+    required this.pageNumber,
+    required this.pageWidth,
+    required this.pageHeight,
+    required this.rects,
+  });
+
+  @override
+  int get hashCode =>
+      pageNumber.hashCode ^
+      pageWidth.hashCode ^
+      pageHeight.hashCode ^
+      rects.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is PageHighlights &&
+          runtimeType == other.runtimeType &&
+          pageNumber == other.pageNumber &&
+          pageWidth == other.pageWidth &&
+          pageHeight == other.pageHeight &&
+          rects == other.rects;
+}
+
+class PdfExtractionReport {
+  final List<TextWithLocation> pages;
+  final PlatformInt64 totalChars;
+  final PlatformInt64 undecodableChars;
+  final double undecodableFraction;
+  final Int32List outlinedTextPages;
+
+  const PdfExtractionReport({
+    required this.pages,
+    required this.totalChars,
+    required this.undecodableChars,
+    required this.undecodableFraction,
+    required this.outlinedTextPages,
+  });
+
+  @override
+  int get hashCode =>
+      pages.hashCode ^
+      totalChars.hashCode ^
+      undecodableChars.hashCode ^
+      undecodableFraction.hashCode ^
+      outlinedTextPages.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is PdfExtractionReport &&
+          runtimeType == other.runtimeType &&
+          pages == other.pages &&
+          totalChars == other.totalChars &&
+          undecodableChars == other.undecodableChars &&
+          undecodableFraction == other.undecodableFraction &&
+          outlinedTextPages == other.outlinedTextPages;
+}
+
 class TextWithLocation {
   final String text;
   final int pageNumber;
@@ -0,0 +1,51 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.7.0.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import '../frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+
+// These functions are ignored because they are not marked as `pub`: `order_by_scores`
+// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `clone`, `fmt`
+
+/// Loads the cross-encoder in `model_path` (model.onnx and tokenizer.json,
+/// e.g. ms-marco-MiniLM-L-6-v2) for `rerank`. Returns false when there is no
+/// model there or the app is built without the onnx feature, and `rerank`
+/// then keeps results in their original order.
+bool initReranker({required String modelPath}) =>
+    RustLib.instance.api.crateApiRerankInitReranker(modelPath: modelPath);
+
+/// Reorders the first `top_n` (20 by default) of `candidates`, the texts of
+/// the top search results, by how well the reranker says each one answers
+/// `query`. Scoring goes down the list and stops before it would overrun
+/// `budget_ms`; candidates it didn't get to follow the scored ones in their
+/// original order, as all of them do without a reranker.
+List<RerankedCandidate> rerank(
+        {required String query,
+        required List<String> candidates,
+        required int? budgetMs,
+        required int? topN}) =>
+    RustLib.instance.api.crateApiRerankRerank(
+        query: query, candidates: candidates, budgetMs: budgetMs, topN: topN);
+
+class RerankedCandidate {
+  final int index;
+  final double? score;
+
+  const RerankedCandidate({
+    required this.index,
+    required this.score,
+  });
+
+  @override
+  int get hashCode => index.hashCode ^ score.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is RerankedCandidate &&
+          runtimeType == other.runtimeType &&
+          index == other.index &&
+          score == other.score;
+}
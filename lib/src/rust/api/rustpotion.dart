@@ -4,17 +4,219 @@
 // ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
 
 import '../frb_generated.dart';
+import 'embedding.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
 
+// These functions are ignored because they are not marked as `pub`: `get_potion`, `potion_backend`, `set_potion`
 // These types are ignored because they are not used by any `pub` functions: `PotionWrapper`
+// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `clone`, `fmt`
 
-bool initPotion({required String appDir}) =>
-    RustLib.instance.api.crateApiRustpotionInitPotion(appDir: appDir);
+/// Sets how many tokens of a text `model_id` embeds. With `sliding_window_stride`,
+/// long texts are embedded as windows of `max_tokens` tokens that start every
+/// `sliding_window_stride` tokens, and the windows are averaged, so nothing is
+/// cut off. A `max_tokens` of 0 embeds every token in one go. Other models keep
+/// their budget, and swapping the model resets it to 512 tokens.
+bool setEmbeddingTokenBudget(
+        {required String modelId,
+        required int maxTokens,
+        required int? slidingWindowStride}) =>
+    RustLib.instance.api.crateApiRustpotionSetEmbeddingTokenBudget(
+        modelId: modelId,
+        maxTokens: maxTokens,
+        slidingWindowStride: slidingWindowStride);
 
-Float64List getEmbeddingFromRustpotion({required String text}) =>
+/// Loads a model2vec-format directory (model.safetensors, tokenizer.json and
+/// optionally config.json), e.g. `<app dir>/models/RETRIEVAL32M`, as `model_id`.
+bool initPotion({required String modelId, required String modelPath}) =>
     RustLib.instance.api
-        .crateApiRustpotionGetEmbeddingFromRustpotion(text: text);
+        .crateApiRustpotionInitPotion(modelId: modelId, modelPath: modelPath);
 
-List<Float64List> getEmbeddingsFromRustpotion({required List<String> texts}) =>
+/// Like `init_potion`, but returns `dimensions`-long embeddings: projected onto
+/// the PCA components in `<model_path>/pca.safetensors` when that file exists,
+/// otherwise truncated (for Matryoshka-style models).
+bool initPotionReduced(
+        {required String modelId,
+        required String modelPath,
+        required int dimensions}) =>
+    RustLib.instance.api.crateApiRustpotionInitPotionReduced(
+        modelId: modelId, modelPath: modelPath, dimensions: dimensions);
+
+/// Like `init_potion`, pooling with the token weights saved by `compute_token_weights`
+bool initPotionWeighted(
+        {required String modelId,
+        required String modelPath,
+        required String weightsFile}) =>
+    RustLib.instance.api.crateApiRustpotionInitPotionWeighted(
+        modelId: modelId, modelPath: modelPath, weightsFile: weightsFile);
+
+/// Like `init_potion`, for model files that don't use the model2vec file names
+bool initPotionFromFiles(
+        {required String modelId,
+        required String modelFile,
+        required String tokenizerFile,
+        required String? configFile}) =>
+    RustLib.instance.api.crateApiRustpotionInitPotionFromFiles(
+        modelId: modelId,
+        modelFile: modelFile,
+        tokenizerFile: tokenizerFile,
+        configFile: configFile);
+
+/// Like `init_potion`, from model files already loaded in memory, e.g. Flutter
+/// assets read with `rootBundle.load`, so they don't have to be copied first.
+bool initPotionFromBytes(
+        {required String modelId,
+        required List<int> modelBytes,
+        required List<int> tokenizerBytes,
+        required List<int>? configBytes}) =>
+    RustLib.instance.api.crateApiRustpotionInitPotionFromBytes(
+        modelId: modelId,
+        modelBytes: modelBytes,
+        tokenizerBytes: tokenizerBytes,
+        configBytes: configBytes);
+
+/// Loads `model_path` and puts it in place of whatever `model_id` was, e.g.
+/// after a model upgrade. The old model keeps serving calls already running.
+bool swapPotion({required String modelId, required String modelPath}) =>
+    RustLib.instance.api
+        .crateApiRustpotionSwapPotion(modelId: modelId, modelPath: modelPath);
+
+/// Drops `model_id`, false if it wasn't loaded
+bool unloadPotion({required String modelId}) =>
+    RustLib.instance.api.crateApiRustpotionUnloadPotion(modelId: modelId);
+
+/// Ids of the models that are currently loaded
+List<String> loadedPotionModels() =>
+    RustLib.instance.api.crateApiRustpotionLoadedPotionModels();
+
+/// Length of the embeddings `model_id` returns
+int potionDimensions({required String modelId}) =>
+    RustLib.instance.api.crateApiRustpotionPotionDimensions(modelId: modelId);
+
+/// Saves which model (manifest.json of its directory) and vector size built
+/// the index at `index_manifest_file`, for `check_index_model`.
+bool recordIndexModel(
+        {required String modelId, required String indexManifestFile}) =>
+    RustLib.instance.api.crateApiRustpotionRecordIndexModel(
+        modelId: modelId, indexManifestFile: indexManifestFile);
+
+/// True if `model_id` built the index recorded in `index_manifest_file`, false
+/// if nothing was recorded yet, and an error saying what differs otherwise.
+bool checkIndexModel(
+        {required String modelId, required String indexManifestFile}) =>
+    RustLib.instance.api.crateApiRustpotionCheckIndexModel(
+        modelId: modelId, indexManifestFile: indexManifestFile);
+
+Float64List getEmbeddingFromRustpotion(
+        {required String modelId,
+        required String text,
+        required EmbeddingRole role}) =>
+    RustLib.instance.api.crateApiRustpotionGetEmbeddingFromRustpotion(
+        modelId: modelId, text: text, role: role);
+
+List<Float64List> getEmbeddingsFromRustpotion(
+        {required String modelId,
+        required List<String> texts,
+        required EmbeddingRole role}) =>
+    RustLib.instance.api.crateApiRustpotionGetEmbeddingsFromRustpotion(
+        modelId: modelId, texts: texts, role: role);
+
+/// Why `text` matched `query`: the `top_k` words on each side that add the most
+/// to the cosine similarity, so a result snippet can highlight them even when
+/// they don't appear in the query.
+MatchExplanation explainMatch(
+        {required String modelId,
+        required String query,
+        required String text,
+        required int topK}) =>
+    RustLib.instance.api.crateApiRustpotionExplainMatch(
+        modelId: modelId, query: query, text: text, topK: topK);
+
+/// Collects the words of the indexed section texts that `nearest_corpus_words`
+/// can suggest for `model_id`, replacing any earlier set. Returns how many.
+Future<int> buildCorpusWords(
+        {required String modelId, required List<String> texts}) =>
     RustLib.instance.api
-        .crateApiRustpotionGetEmbeddingsFromRustpotion(texts: texts);
+        .crateApiRustpotionBuildCorpusWords(modelId: modelId, texts: texts);
+
+/// The `k` corpus words closest in meaning to `term` (e.g. "bleeding" ->
+/// "hemorrhage"), for suggesting or adding synonyms to an exact-phrase search.
+List<SimilarWord> nearestCorpusWords(
+        {required String modelId, required String term, required int k}) =>
+    RustLib.instance.api.crateApiRustpotionNearestCorpusWords(
+        modelId: modelId, term: term, k: k);
+
+/// Computes IDF token weights from the indexed section texts with `model_id`
+/// and saves them to `weights_file`, for `init_potion_weighted`.
+Future<bool> computeTokenWeights(
+        {required String modelId,
+        required List<String> texts,
+        required String weightsFile}) =>
+    RustLib.instance.api.crateApiRustpotionComputeTokenWeights(
+        modelId: modelId, texts: texts, weightsFile: weightsFile);
+
+class MatchExplanation {
+  final double similarity;
+  final List<MatchWord> queryWords;
+  final List<MatchWord> textWords;
+
+  const MatchExplanation({
+    required this.similarity,
+    required this.queryWords,
+    required this.textWords,
+  });
+
+  @override
+  int get hashCode =>
+      similarity.hashCode ^ queryWords.hashCode ^ textWords.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is MatchExplanation &&
+          runtimeType == other.runtimeType &&
+          similarity == other.similarity &&
+          queryWords == other.queryWords &&
+          textWords == other.textWords;
+}
+
+class MatchWord {
+  final String word;
+  final double score;
+
+  const MatchWord({
+    required this.word,
+    required this.score,
+  });
+
+  @override
+  int get hashCode => word.hashCode ^ score.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is MatchWord &&
+          runtimeType == other.runtimeType &&
+          word == other.word &&
+          score == other.score;
+}
+
+class SimilarWord {
+  final String word;
+  final double similarity;
+
+  const SimilarWord({
+    required this.word,
+    required this.similarity,
+  });
+
+  @override
+  int get hashCode => word.hashCode ^ similarity.hashCode;
+
+  @override
+  bool operator ==(Object other) =>
+      identical(this, other) ||
+      other is SimilarWord &&
+          runtimeType == other.runtimeType &&
+          word == other.word &&
+          similarity == other.similarity;
+}
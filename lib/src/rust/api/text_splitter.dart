@@ -12,11 +12,16 @@ List<String> splitText({required String text, required int maxChars}) =>
     RustLib.instance.api
         .crateApiTextSplitterSplitText(text: text, maxChars: maxChars);
 
-List<String> semanticChunking({required String text, required int maxChars}) =>
-    RustLib.instance.api
-        .crateApiTextSplitterSemanticChunking(text: text, maxChars: maxChars);
+List<String> semanticChunking(
+        {required String modelId,
+        required String text,
+        required int maxChars}) =>
+    RustLib.instance.api.crateApiTextSplitterSemanticChunking(
+        modelId: modelId, text: text, maxChars: maxChars);
 
 List<String> semanticChunkingV2(
-        {required String text, required int minChunkSize}) =>
+        {required String modelId,
+        required String text,
+        required int minChunkSize}) =>
     RustLib.instance.api.crateApiTextSplitterSemanticChunkingV2(
-        text: text, minChunkSize: minChunkSize);
+        modelId: modelId, text: text, minChunkSize: minChunkSize);
@@ -3,9 +3,12 @@
 
 // ignore_for_file: unused_import, unused_element, unnecessary_import, duplicate_ignore, invalid_use_of_internal_member, annotate_overrides, non_constant_identifier_names, curly_braces_in_flow_control_structures, prefer_const_literals_to_create_immutables, unused_field
 
+import 'api/embedding.dart';
 import 'api/fast_html2md_functions.dart';
 import 'api/monolith_functions.dart';
+import 'api/ort_functions.dart';
 import 'api/pdf_text_extractor.dart';
+import 'api/rerank.dart';
 import 'api/rustpotion.dart';
 import 'api/simple.dart';
 import 'api/text_splitter.dart';
@@ -74,7 +77,7 @@ class RustLib extends BaseEntrypoint<RustLibApi, RustLibApiImpl, RustLibWire> {
   String get codegenVersion => '2.7.0';
 
   @override
-  int get rustContentHash => 278893951;
+  int get rustContentHash => 697897874;
 
   static const kDefaultExternalLibraryLoaderConfig =
       ExternalLibraryLoaderConfig(
@@ -85,10 +88,36 @@ class RustLib extends BaseEntrypoint<RustLibApi, RustLibApiImpl, RustLibWire> {
 }
 
 abstract class RustLibApi extends BaseApi {
+  Future<int> crateApiRustpotionBuildCorpusWords(
+      {required String modelId, required List<String> texts});
+
+  bool crateApiRustpotionCheckIndexModel(
+      {required String modelId, required String indexManifestFile});
+
+  bool crateApiEmbeddingClearEmbeddingCache();
+
+  Future<bool> crateApiRustpotionComputeTokenWeights(
+      {required String modelId,
+      required List<String> texts,
+      required String weightsFile});
+
   bool crateApiMonolithFunctionsDownloadWebPage({required String url});
 
+  EmbeddingCacheStats crateApiEmbeddingEmbeddingCacheStats();
+
+  int crateApiEmbeddingEmbeddingDimensions({required String modelId});
+
   TokenizerOutput crateApiTokenizerEncodeText({required String input});
 
+  MatchExplanation crateApiRustpotionExplainMatch(
+      {required String modelId,
+      required String query,
+      required String text,
+      required int topK});
+
+  String crateApiPdfTextExtractorExtractPageLayoutJson(
+      {required List<int> pdfBytes, required int pageNumber});
+
   List<TextWithLocation> crateApiPdfTextExtractorExtractTextFromPdf(
       {required List<int> pdfBytes});
 
@@ -99,11 +128,33 @@ abstract class RustLibApi extends BaseApi {
       crateApiPdfTextExtractorExtractTextFromPdfExtractMultithreaded(
           {required List<int> pdfBytes});
 
+  PdfExtractionReport crateApiPdfTextExtractorExtractTextFromPdfWithReport(
+      {required List<int> pdfBytes});
+
+  PageHighlights crateApiPdfTextExtractorFindTextHighlights(
+      {required List<int> pdfBytes,
+      required int pageNumber,
+      required List<String> needles});
+
+  Float64List crateApiEmbeddingGetEmbedding(
+      {required String modelId,
+      required String text,
+      required EmbeddingRole role});
+
   Float64List crateApiRustpotionGetEmbeddingFromRustpotion(
-      {required String text});
+      {required String modelId,
+      required String text,
+      required EmbeddingRole role});
+
+  List<Float64List> crateApiEmbeddingGetEmbeddings(
+      {required String modelId,
+      required List<String> texts,
+      required EmbeddingRole role});
 
   List<Float64List> crateApiRustpotionGetEmbeddingsFromRustpotion(
-      {required List<String> texts});
+      {required String modelId,
+      required List<String> texts,
+      required EmbeddingRole role});
 
   String crateApiSimpleGreet({required String name});
 
@@ -127,18 +178,89 @@ abstract class RustLibApi extends BaseApi {
 
   Future<void> crateApiSimpleInitApp();
 
-  bool crateApiRustpotionInitPotion({required String appDir});
+  bool crateApiEmbeddingInitEmbeddingCache(
+      {required String cacheDir, required int maxMegabytes});
+
+  bool crateApiEmbeddingInitEmbeddingModel(
+      {required String modelId, required String modelPath});
+
+  bool crateApiOrtFunctionsInitModel(
+      {required String modelId, required String modelPath});
+
+  bool crateApiOrtFunctionsInitOnnxRuntime({required String libraryPath});
+
+  bool crateApiRustpotionInitPotion(
+      {required String modelId, required String modelPath});
+
+  bool crateApiRustpotionInitPotionFromBytes(
+      {required String modelId,
+      required List<int> modelBytes,
+      required List<int> tokenizerBytes,
+      required List<int>? configBytes});
+
+  bool crateApiRustpotionInitPotionFromFiles(
+      {required String modelId,
+      required String modelFile,
+      required String tokenizerFile,
+      required String? configFile});
+
+  bool crateApiRustpotionInitPotionReduced(
+      {required String modelId,
+      required String modelPath,
+      required int dimensions});
+
+  bool crateApiRustpotionInitPotionWeighted(
+      {required String modelId,
+      required String modelPath,
+      required String weightsFile});
+
+  bool crateApiRerankInitReranker({required String modelPath});
 
   bool crateApiTokenizerInitTokenizer({required String tokenizerPath});
 
+  List<String> crateApiEmbeddingLoadedEmbeddingModels();
+
+  List<String> crateApiRustpotionLoadedPotionModels();
+
+  List<SimilarWord> crateApiRustpotionNearestCorpusWords(
+      {required String modelId, required String term, required int k});
+
+  int crateApiRustpotionPotionDimensions({required String modelId});
+
+  bool crateApiRustpotionRecordIndexModel(
+      {required String modelId, required String indexManifestFile});
+
+  List<RerankedCandidate> crateApiRerankRerank(
+      {required String query,
+      required List<String> candidates,
+      required int? budgetMs,
+      required int? topN});
+
   List<String> crateApiTextSplitterSemanticChunking(
-      {required String text, required int maxChars});
+      {required String modelId, required String text, required int maxChars});
 
   List<String> crateApiTextSplitterSemanticChunkingV2(
-      {required String text, required int minChunkSize});
+      {required String modelId,
+      required String text,
+      required int minChunkSize});
+
+  bool crateApiRustpotionSetEmbeddingTokenBudget(
+      {required String modelId,
+      required int maxTokens,
+      required int? slidingWindowStride});
 
   List<String> crateApiTextSplitterSplitText(
       {required String text, required int maxChars});
+
+  bool crateApiEmbeddingSwapEmbeddingModel(
+      {required String modelId, required String modelPath});
+
+  bool crateApiRustpotionSwapPotion(
+      {required String modelId, required String modelPath});
+
+  bool crateApiEmbeddingUnloadEmbeddingModel({required String modelId});
+
+  bool crateApiRustpotionUnloadPotion({required String modelId});
 }
 
 class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
@@ -149,13 +271,119 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     required super.portManager,
   });
 
+  @override
+  Future<int> crateApiRustpotionBuildCorpusWords(
+      {required String modelId, required List<String> texts}) {
+    return handler.executeNormal(NormalTask(
+      callFfi: (port_) {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_list_String(texts, serializer);
+        pdeCallFfi(generalizedFrbRustBinding, serializer,
+            funcId: 1, port: port_);
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_i_32,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRustpotionBuildCorpusWordsConstMeta,
+      argValues: [modelId, texts],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionBuildCorpusWordsConstMeta =>
+      const TaskConstMeta(
+        debugName: "build_corpus_words",
+        argNames: ["modelId", "texts"],
+      );
+
+  @override
+  bool crateApiRustpotionCheckIndexModel(
+      {required String modelId, required String indexManifestFile}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(indexManifestFile, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 2)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRustpotionCheckIndexModelConstMeta,
+      argValues: [modelId, indexManifestFile],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionCheckIndexModelConstMeta =>
+      const TaskConstMeta(
+        debugName: "check_index_model",
+        argNames: ["modelId", "indexManifestFile"],
+      );
+
+  @override
+  bool crateApiEmbeddingClearEmbeddingCache() {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 3)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiEmbeddingClearEmbeddingCacheConstMeta,
+      argValues: [],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiEmbeddingClearEmbeddingCacheConstMeta =>
+      const TaskConstMeta(
+        debugName: "clear_embedding_cache",
+        argNames: [],
+      );
+
+  @override
+  Future<bool> crateApiRustpotionComputeTokenWeights(
+      {required String modelId,
+      required List<String> texts,
+      required String weightsFile}) {
+    return handler.executeNormal(NormalTask(
+      callFfi: (port_) {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_list_String(texts, serializer);
+        sse_encode_String(weightsFile, serializer);
+        pdeCallFfi(generalizedFrbRustBinding, serializer,
+            funcId: 4, port: port_);
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRustpotionComputeTokenWeightsConstMeta,
+      argValues: [modelId, texts, weightsFile],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionComputeTokenWeightsConstMeta =>
+      const TaskConstMeta(
+        debugName: "compute_token_weights",
+        argNames: ["modelId", "texts", "weightsFile"],
+      );
+
   @override
   bool crateApiMonolithFunctionsDownloadWebPage({required String url}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
         sse_encode_String(url, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 1)!;
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 5)!;
       },
       codec: SseCodec(
         decodeSuccessData: sse_decode_bool,
@@ -173,13 +401,60 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
         argNames: ["url"],
       );
 
+  @override
+  EmbeddingCacheStats crateApiEmbeddingEmbeddingCacheStats() {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 6)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_embedding_cache_stats,
+        decodeErrorData: null,
+      ),
+      constMeta: kCrateApiEmbeddingEmbeddingCacheStatsConstMeta,
+      argValues: [],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiEmbeddingEmbeddingCacheStatsConstMeta =>
+      const TaskConstMeta(
+        debugName: "embedding_cache_stats",
+        argNames: [],
+      );
+
+  @override
+  int crateApiEmbeddingEmbeddingDimensions({required String modelId}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 7)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_i_32,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiEmbeddingEmbeddingDimensionsConstMeta,
+      argValues: [modelId],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiEmbeddingEmbeddingDimensionsConstMeta =>
+      const TaskConstMeta(
+        debugName: "embedding_dimensions",
+        argNames: ["modelId"],
+      );
+
   @override
   TokenizerOutput crateApiTokenizerEncodeText({required String input}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
         sse_encode_String(input, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 2)!;
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 8)!;
       },
       codec: SseCodec(
         decodeSuccessData: sse_decode_tokenizer_output,
@@ -197,6 +472,63 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
         argNames: ["input"],
       );
 
+  @override
+  MatchExplanation crateApiRustpotionExplainMatch(
+      {required String modelId,
+      required String query,
+      required String text,
+      required int topK}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(query, serializer);
+        sse_encode_String(text, serializer);
+        sse_encode_i_32(topK, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 9)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_match_explanation,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRustpotionExplainMatchConstMeta,
+      argValues: [modelId, query, text, topK],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionExplainMatchConstMeta =>
+      const TaskConstMeta(
+        debugName: "explain_match",
+        argNames: ["modelId", "query", "text", "topK"],
+      );
+
+  @override
+  String crateApiPdfTextExtractorExtractPageLayoutJson(
+      {required List<int> pdfBytes, required int pageNumber}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_list_prim_u_8_loose(pdfBytes, serializer);
+        sse_encode_i_32(pageNumber, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 10)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_String,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiPdfTextExtractorExtractPageLayoutJsonConstMeta,
+      argValues: [pdfBytes, pageNumber],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiPdfTextExtractorExtractPageLayoutJsonConstMeta =>
+      const TaskConstMeta(
+        debugName: "extract_page_layout_json",
+        argNames: ["pdfBytes", "pageNumber"],
+      );
+
   @override
   List<TextWithLocation> crateApiPdfTextExtractorExtractTextFromPdf(
       {required List<int> pdfBytes}) {
@@ -204,7 +536,7 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
         sse_encode_list_prim_u_8_loose(pdfBytes, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 3)!;
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 11)!;
       },
       codec: SseCodec(
         decodeSuccessData: sse_decode_list_text_with_location,
@@ -229,7 +561,7 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
         sse_encode_list_prim_u_8_loose(pdfBytes, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 4)!;
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 12)!;
       },
       codec: SseCodec(
         decodeSuccessData: sse_decode_list_text_with_location,
@@ -256,7 +588,7 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
         sse_encode_list_prim_u_8_loose(pdfBytes, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 5)!;
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 13)!;
       },
       codec: SseCodec(
         decodeSuccessData: sse_decode_list_text_with_location,
@@ -277,130 +609,251 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
           );
 
   @override
-  Float64List crateApiRustpotionGetEmbeddingFromRustpotion(
-      {required String text}) {
+  PdfExtractionReport crateApiPdfTextExtractorExtractTextFromPdfWithReport(
+      {required List<int> pdfBytes}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(text, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 6)!;
+        sse_encode_list_prim_u_8_loose(pdfBytes, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 14)!;
       },
       codec: SseCodec(
-        decodeSuccessData: sse_decode_list_prim_f_64_strict,
+        decodeSuccessData: sse_decode_pdf_extraction_report,
         decodeErrorData: sse_decode_AnyhowException,
       ),
-      constMeta: kCrateApiRustpotionGetEmbeddingFromRustpotionConstMeta,
-      argValues: [text],
+      constMeta: kCrateApiPdfTextExtractorExtractTextFromPdfWithReportConstMeta,
+      argValues: [pdfBytes],
       apiImpl: this,
     ));
   }
 
-  TaskConstMeta get kCrateApiRustpotionGetEmbeddingFromRustpotionConstMeta =>
-      const TaskConstMeta(
-        debugName: "get_embedding_from_rustpotion",
-        argNames: ["text"],
-      );
+  TaskConstMeta
+      get kCrateApiPdfTextExtractorExtractTextFromPdfWithReportConstMeta =>
+          const TaskConstMeta(
+            debugName: "extract_text_from_pdf_with_report",
+            argNames: ["pdfBytes"],
+          );
 
   @override
-  List<Float64List> crateApiRustpotionGetEmbeddingsFromRustpotion(
-      {required List<String> texts}) {
+  PageHighlights crateApiPdfTextExtractorFindTextHighlights(
+      {required List<int> pdfBytes,
+      required int pageNumber,
+      required List<String> needles}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_list_String(texts, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 7)!;
+        sse_encode_list_prim_u_8_loose(pdfBytes, serializer);
+        sse_encode_i_32(pageNumber, serializer);
+        sse_encode_list_String(needles, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 15)!;
       },
       codec: SseCodec(
-        decodeSuccessData: sse_decode_list_list_prim_f_64_strict,
+        decodeSuccessData: sse_decode_page_highlights,
         decodeErrorData: sse_decode_AnyhowException,
       ),
-      constMeta: kCrateApiRustpotionGetEmbeddingsFromRustpotionConstMeta,
-      argValues: [texts],
+      constMeta: kCrateApiPdfTextExtractorFindTextHighlightsConstMeta,
+      argValues: [pdfBytes, pageNumber, needles],
       apiImpl: this,
     ));
   }
 
-  TaskConstMeta get kCrateApiRustpotionGetEmbeddingsFromRustpotionConstMeta =>
+  TaskConstMeta get kCrateApiPdfTextExtractorFindTextHighlightsConstMeta =>
       const TaskConstMeta(
-        debugName: "get_embeddings_from_rustpotion",
-        argNames: ["texts"],
+        debugName: "find_text_highlights",
+        argNames: ["pdfBytes", "pageNumber", "needles"],
       );
 
   @override
-  String crateApiSimpleGreet({required String name}) {
+  Float64List crateApiEmbeddingGetEmbedding(
+      {required String modelId,
+      required String text,
+      required EmbeddingRole role}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(name, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 8)!;
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(text, serializer);
+        sse_encode_embedding_role(role, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 16)!;
       },
       codec: SseCodec(
-        decodeSuccessData: sse_decode_String,
-        decodeErrorData: null,
+        decodeSuccessData: sse_decode_list_prim_f_64_strict,
+        decodeErrorData: sse_decode_AnyhowException,
       ),
-      constMeta: kCrateApiSimpleGreetConstMeta,
-      argValues: [name],
+      constMeta: kCrateApiEmbeddingGetEmbeddingConstMeta,
+      argValues: [modelId, text, role],
       apiImpl: this,
     ));
   }
 
-  TaskConstMeta get kCrateApiSimpleGreetConstMeta => const TaskConstMeta(
-        debugName: "greet",
-        argNames: ["name"],
+  TaskConstMeta get kCrateApiEmbeddingGetEmbeddingConstMeta =>
+      const TaskConstMeta(
+        debugName: "get_embedding",
+        argNames: ["modelId", "text", "role"],
       );
 
   @override
-  String crateApiFastHtml2MdFunctionsHtmlToMarkdown(
-      {required String htmlContent, required String outputPath}) {
+  Float64List crateApiRustpotionGetEmbeddingFromRustpotion(
+      {required String modelId,
+      required String text,
+      required EmbeddingRole role}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(htmlContent, serializer);
-        sse_encode_String(outputPath, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 9)!;
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(text, serializer);
+        sse_encode_embedding_role(role, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 17)!;
       },
       codec: SseCodec(
-        decodeSuccessData: sse_decode_String,
+        decodeSuccessData: sse_decode_list_prim_f_64_strict,
         decodeErrorData: sse_decode_AnyhowException,
       ),
-      constMeta: kCrateApiFastHtml2MdFunctionsHtmlToMarkdownConstMeta,
-      argValues: [htmlContent, outputPath],
+      constMeta: kCrateApiRustpotionGetEmbeddingFromRustpotionConstMeta,
+      argValues: [modelId, text, role],
       apiImpl: this,
     ));
   }
 
-  TaskConstMeta get kCrateApiFastHtml2MdFunctionsHtmlToMarkdownConstMeta =>
+  TaskConstMeta get kCrateApiRustpotionGetEmbeddingFromRustpotionConstMeta =>
       const TaskConstMeta(
-        debugName: "html_to_markdown",
-        argNames: ["htmlContent", "outputPath"],
+        debugName: "get_embedding_from_rustpotion",
+        argNames: ["modelId", "text", "role"],
       );
 
   @override
-  String crateApiFastHtml2MdFunctionsHtmlToText(
-      {required String htmlContent,
-      required String outputPath,
-      required BigInt width}) {
+  List<Float64List> crateApiEmbeddingGetEmbeddings(
+      {required String modelId,
+      required List<String> texts,
+      required EmbeddingRole role}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(htmlContent, serializer);
-        sse_encode_String(outputPath, serializer);
-        sse_encode_usize(width, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 10)!;
+        sse_encode_String(modelId, serializer);
+        sse_encode_list_String(texts, serializer);
+        sse_encode_embedding_role(role, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 18)!;
       },
       codec: SseCodec(
-        decodeSuccessData: sse_decode_String,
+        decodeSuccessData: sse_decode_list_list_prim_f_64_strict,
         decodeErrorData: sse_decode_AnyhowException,
       ),
-      constMeta: kCrateApiFastHtml2MdFunctionsHtmlToTextConstMeta,
-      argValues: [htmlContent, outputPath, width],
+      constMeta: kCrateApiEmbeddingGetEmbeddingsConstMeta,
+      argValues: [modelId, texts, role],
       apiImpl: this,
     ));
   }
 
-  TaskConstMeta get kCrateApiFastHtml2MdFunctionsHtmlToTextConstMeta =>
+  TaskConstMeta get kCrateApiEmbeddingGetEmbeddingsConstMeta =>
       const TaskConstMeta(
-        debugName: "html_to_text",
+        debugName: "get_embeddings",
+        argNames: ["modelId", "texts", "role"],
+      );
+
+  @override
+  List<Float64List> crateApiRustpotionGetEmbeddingsFromRustpotion(
+      {required String modelId,
+      required List<String> texts,
+      required EmbeddingRole role}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_list_String(texts, serializer);
+        sse_encode_embedding_role(role, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 19)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_list_list_prim_f_64_strict,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRustpotionGetEmbeddingsFromRustpotionConstMeta,
+      argValues: [modelId, texts, role],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionGetEmbeddingsFromRustpotionConstMeta =>
+      const TaskConstMeta(
+        debugName: "get_embeddings_from_rustpotion",
+        argNames: ["modelId", "texts", "role"],
+      );
+
+  @override
+  String crateApiSimpleGreet({required String name}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(name, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 20)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_String,
+        decodeErrorData: null,
+      ),
+      constMeta: kCrateApiSimpleGreetConstMeta,
+      argValues: [name],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiSimpleGreetConstMeta => const TaskConstMeta(
+        debugName: "greet",
+        argNames: ["name"],
+      );
+
+  @override
+  String crateApiFastHtml2MdFunctionsHtmlToMarkdown(
+      {required String htmlContent, required String outputPath}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(htmlContent, serializer);
+        sse_encode_String(outputPath, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 21)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_String,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiFastHtml2MdFunctionsHtmlToMarkdownConstMeta,
+      argValues: [htmlContent, outputPath],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiFastHtml2MdFunctionsHtmlToMarkdownConstMeta =>
+      const TaskConstMeta(
+        debugName: "html_to_markdown",
+        argNames: ["htmlContent", "outputPath"],
+      );
+
+  @override
+  String crateApiFastHtml2MdFunctionsHtmlToText(
+      {required String htmlContent,
+      required String outputPath,
+      required BigInt width}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(htmlContent, serializer);
+        sse_encode_String(outputPath, serializer);
+        sse_encode_usize(width, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 22)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_String,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiFastHtml2MdFunctionsHtmlToTextConstMeta,
+      argValues: [htmlContent, outputPath, width],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiFastHtml2MdFunctionsHtmlToTextConstMeta =>
+      const TaskConstMeta(
+        debugName: "html_to_text",
         argNames: ["htmlContent", "outputPath", "width"],
       );
 
@@ -415,7 +868,7 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
         sse_encode_String(htmlContent, serializer);
         sse_encode_String(outputPath, serializer);
         sse_encode_usize(width, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 11)!;
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 23)!;
       },
       codec: SseCodec(
         decodeSuccessData: sse_decode_String,
@@ -445,7 +898,7 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
         sse_encode_String(htmlContent, serializer);
         sse_encode_String(outputPath, serializer);
         sse_encode_usize(width, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 12)!;
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 24)!;
       },
       codec: SseCodec(
         decodeSuccessData: sse_decode_String,
@@ -469,7 +922,7 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
       callFfi: (port_) {
         final serializer = SseSerializer(generalizedFrbRustBinding);
         pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 13, port: port_);
+            funcId: 25, port: port_);
       },
       codec: SseCodec(
         decodeSuccessData: sse_decode_unit,
@@ -487,19 +940,123 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
       );
 
   @override
-  bool crateApiRustpotionInitPotion({required String appDir}) {
+  bool crateApiEmbeddingInitEmbeddingCache(
+      {required String cacheDir, required int maxMegabytes}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(appDir, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 14)!;
+        sse_encode_String(cacheDir, serializer);
+        sse_encode_i_32(maxMegabytes, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 26)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiEmbeddingInitEmbeddingCacheConstMeta,
+      argValues: [cacheDir, maxMegabytes],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiEmbeddingInitEmbeddingCacheConstMeta =>
+      const TaskConstMeta(
+        debugName: "init_embedding_cache",
+        argNames: ["cacheDir", "maxMegabytes"],
+      );
+
+  @override
+  bool crateApiEmbeddingInitEmbeddingModel(
+      {required String modelId, required String modelPath}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(modelPath, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 27)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiEmbeddingInitEmbeddingModelConstMeta,
+      argValues: [modelId, modelPath],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiEmbeddingInitEmbeddingModelConstMeta =>
+      const TaskConstMeta(
+        debugName: "init_embedding_model",
+        argNames: ["modelId", "modelPath"],
+      );
+
+  @override
+  bool crateApiOrtFunctionsInitModel(
+      {required String modelId, required String modelPath}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(modelPath, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 28)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiOrtFunctionsInitModelConstMeta,
+      argValues: [modelId, modelPath],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiOrtFunctionsInitModelConstMeta =>
+      const TaskConstMeta(
+        debugName: "init_model",
+        argNames: ["modelId", "modelPath"],
+      );
+
+  @override
+  bool crateApiOrtFunctionsInitOnnxRuntime({required String libraryPath}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(libraryPath, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 29)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiOrtFunctionsInitOnnxRuntimeConstMeta,
+      argValues: [libraryPath],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiOrtFunctionsInitOnnxRuntimeConstMeta =>
+      const TaskConstMeta(
+        debugName: "init_onnx_runtime",
+        argNames: ["libraryPath"],
+      );
+
+  @override
+  bool crateApiRustpotionInitPotion(
+      {required String modelId, required String modelPath}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(modelPath, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 30)!;
       },
       codec: SseCodec(
         decodeSuccessData: sse_decode_bool,
         decodeErrorData: sse_decode_AnyhowException,
       ),
       constMeta: kCrateApiRustpotionInitPotionConstMeta,
-      argValues: [appDir],
+      argValues: [modelId, modelPath],
       apiImpl: this,
     ));
   }
@@ -507,189 +1064,796 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
   TaskConstMeta get kCrateApiRustpotionInitPotionConstMeta =>
       const TaskConstMeta(
         debugName: "init_potion",
-        argNames: ["appDir"],
+        argNames: ["modelId", "modelPath"],
       );
 
   @override
-  bool crateApiTokenizerInitTokenizer({required String tokenizerPath}) {
+  bool crateApiRustpotionInitPotionFromBytes(
+      {required String modelId,
+      required List<int> modelBytes,
+      required List<int> tokenizerBytes,
+      required List<int>? configBytes}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(tokenizerPath, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 15)!;
+        sse_encode_String(modelId, serializer);
+        sse_encode_list_prim_u_8_loose(modelBytes, serializer);
+        sse_encode_list_prim_u_8_loose(tokenizerBytes, serializer);
+        sse_encode_opt_list_prim_u_8_loose(configBytes, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 31)!;
       },
       codec: SseCodec(
         decodeSuccessData: sse_decode_bool,
         decodeErrorData: sse_decode_AnyhowException,
       ),
-      constMeta: kCrateApiTokenizerInitTokenizerConstMeta,
-      argValues: [tokenizerPath],
+      constMeta: kCrateApiRustpotionInitPotionFromBytesConstMeta,
+      argValues: [modelId, modelBytes, tokenizerBytes, configBytes],
       apiImpl: this,
     ));
   }
 
-  TaskConstMeta get kCrateApiTokenizerInitTokenizerConstMeta =>
+  TaskConstMeta get kCrateApiRustpotionInitPotionFromBytesConstMeta =>
       const TaskConstMeta(
-        debugName: "init_tokenizer",
-        argNames: ["tokenizerPath"],
+        debugName: "init_potion_from_bytes",
+        argNames: ["modelId", "modelBytes", "tokenizerBytes", "configBytes"],
       );
 
   @override
-  List<String> crateApiTextSplitterSemanticChunking(
-      {required String text, required int maxChars}) {
+  bool crateApiRustpotionInitPotionFromFiles(
+      {required String modelId,
+      required String modelFile,
+      required String tokenizerFile,
+      required String? configFile}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(text, serializer);
-        sse_encode_i_32(maxChars, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 16)!;
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(modelFile, serializer);
+        sse_encode_String(tokenizerFile, serializer);
+        sse_encode_opt_String(configFile, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 32)!;
       },
       codec: SseCodec(
-        decodeSuccessData: sse_decode_list_String,
+        decodeSuccessData: sse_decode_bool,
         decodeErrorData: sse_decode_AnyhowException,
       ),
-      constMeta: kCrateApiTextSplitterSemanticChunkingConstMeta,
-      argValues: [text, maxChars],
+      constMeta: kCrateApiRustpotionInitPotionFromFilesConstMeta,
+      argValues: [modelId, modelFile, tokenizerFile, configFile],
       apiImpl: this,
     ));
   }
 
-  TaskConstMeta get kCrateApiTextSplitterSemanticChunkingConstMeta =>
+  TaskConstMeta get kCrateApiRustpotionInitPotionFromFilesConstMeta =>
       const TaskConstMeta(
-        debugName: "semantic_chunking",
-        argNames: ["text", "maxChars"],
+        debugName: "init_potion_from_files",
+        argNames: ["modelId", "modelFile", "tokenizerFile", "configFile"],
       );
 
   @override
-  List<String> crateApiTextSplitterSemanticChunkingV2(
-      {required String text, required int minChunkSize}) {
+  bool crateApiRustpotionInitPotionReduced(
+      {required String modelId,
+      required String modelPath,
+      required int dimensions}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(text, serializer);
-        sse_encode_i_32(minChunkSize, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 17)!;
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(modelPath, serializer);
+        sse_encode_i_32(dimensions, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 33)!;
       },
       codec: SseCodec(
-        decodeSuccessData: sse_decode_list_String,
+        decodeSuccessData: sse_decode_bool,
         decodeErrorData: sse_decode_AnyhowException,
       ),
-      constMeta: kCrateApiTextSplitterSemanticChunkingV2ConstMeta,
-      argValues: [text, minChunkSize],
+      constMeta: kCrateApiRustpotionInitPotionReducedConstMeta,
+      argValues: [modelId, modelPath, dimensions],
       apiImpl: this,
     ));
   }
 
-  TaskConstMeta get kCrateApiTextSplitterSemanticChunkingV2ConstMeta =>
+  TaskConstMeta get kCrateApiRustpotionInitPotionReducedConstMeta =>
       const TaskConstMeta(
-        debugName: "semantic_chunking_v2",
-        argNames: ["text", "minChunkSize"],
+        debugName: "init_potion_reduced",
+        argNames: ["modelId", "modelPath", "dimensions"],
       );
 
   @override
-  List<String> crateApiTextSplitterSplitText(
-      {required String text, required int maxChars}) {
+  bool crateApiRustpotionInitPotionWeighted(
+      {required String modelId,
+      required String modelPath,
+      required String weightsFile}) {
     return handler.executeSync(SyncTask(
       callFfi: () {
         final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(text, serializer);
-        sse_encode_i_32(maxChars, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 18)!;
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(modelPath, serializer);
+        sse_encode_String(weightsFile, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 34)!;
       },
       codec: SseCodec(
-        decodeSuccessData: sse_decode_list_String,
+        decodeSuccessData: sse_decode_bool,
         decodeErrorData: sse_decode_AnyhowException,
       ),
-      constMeta: kCrateApiTextSplitterSplitTextConstMeta,
-      argValues: [text, maxChars],
+      constMeta: kCrateApiRustpotionInitPotionWeightedConstMeta,
+      argValues: [modelId, modelPath, weightsFile],
       apiImpl: this,
     ));
   }
 
-  TaskConstMeta get kCrateApiTextSplitterSplitTextConstMeta =>
+  TaskConstMeta get kCrateApiRustpotionInitPotionWeightedConstMeta =>
       const TaskConstMeta(
-        debugName: "split_text",
-        argNames: ["text", "maxChars"],
+        debugName: "init_potion_weighted",
+        argNames: ["modelId", "modelPath", "weightsFile"],
       );
 
-  @protected
-  AnyhowException dco_decode_AnyhowException(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return AnyhowException(raw as String);
+  @override
+  bool crateApiRerankInitReranker({required String modelPath}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelPath, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 35)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRerankInitRerankerConstMeta,
+      argValues: [modelPath],
+      apiImpl: this,
+    ));
   }
 
-  @protected
-  String dco_decode_String(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as String;
-  }
+  TaskConstMeta get kCrateApiRerankInitRerankerConstMeta => const TaskConstMeta(
+        debugName: "init_reranker",
+        argNames: ["modelPath"],
+      );
 
-  @protected
-  bool dco_decode_bool(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as bool;
+  @override
+  bool crateApiTokenizerInitTokenizer({required String tokenizerPath}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(tokenizerPath, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 36)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiTokenizerInitTokenizerConstMeta,
+      argValues: [tokenizerPath],
+      apiImpl: this,
+    ));
   }
 
-  @protected
-  double dco_decode_f_64(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as double;
+  TaskConstMeta get kCrateApiTokenizerInitTokenizerConstMeta =>
+      const TaskConstMeta(
+        debugName: "init_tokenizer",
+        argNames: ["tokenizerPath"],
+      );
+
+  @override
+  List<String> crateApiEmbeddingLoadedEmbeddingModels() {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 37)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_list_String,
+        decodeErrorData: null,
+      ),
+      constMeta: kCrateApiEmbeddingLoadedEmbeddingModelsConstMeta,
+      argValues: [],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiEmbeddingLoadedEmbeddingModelsConstMeta =>
+      const TaskConstMeta(
+        debugName: "loaded_embedding_models",
+        argNames: [],
+      );
+
+  @override
+  List<String> crateApiRustpotionLoadedPotionModels() {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 38)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_list_String,
+        decodeErrorData: null,
+      ),
+      constMeta: kCrateApiRustpotionLoadedPotionModelsConstMeta,
+      argValues: [],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionLoadedPotionModelsConstMeta =>
+      const TaskConstMeta(
+        debugName: "loaded_potion_models",
+        argNames: [],
+      );
+
+  @override
+  List<SimilarWord> crateApiRustpotionNearestCorpusWords(
+      {required String modelId, required String term, required int k}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(term, serializer);
+        sse_encode_i_32(k, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 39)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_list_similar_word,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRustpotionNearestCorpusWordsConstMeta,
+      argValues: [modelId, term, k],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionNearestCorpusWordsConstMeta =>
+      const TaskConstMeta(
+        debugName: "nearest_corpus_words",
+        argNames: ["modelId", "term", "k"],
+      );
+
+  @override
+  int crateApiRustpotionPotionDimensions({required String modelId}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 40)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_i_32,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRustpotionPotionDimensionsConstMeta,
+      argValues: [modelId],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionPotionDimensionsConstMeta =>
+      const TaskConstMeta(
+        debugName: "potion_dimensions",
+        argNames: ["modelId"],
+      );
+
+  @override
+  bool crateApiRustpotionRecordIndexModel(
+      {required String modelId, required String indexManifestFile}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(indexManifestFile, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 41)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRustpotionRecordIndexModelConstMeta,
+      argValues: [modelId, indexManifestFile],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionRecordIndexModelConstMeta =>
+      const TaskConstMeta(
+        debugName: "record_index_model",
+        argNames: ["modelId", "indexManifestFile"],
+      );
+
+  @override
+  List<RerankedCandidate> crateApiRerankRerank(
+      {required String query,
+      required List<String> candidates,
+      required int? budgetMs,
+      required int? topN}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(query, serializer);
+        sse_encode_list_String(candidates, serializer);
+        sse_encode_opt_box_autoadd_i_32(budgetMs, serializer);
+        sse_encode_opt_box_autoadd_i_32(topN, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 42)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_list_reranked_candidate,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRerankRerankConstMeta,
+      argValues: [query, candidates, budgetMs, topN],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRerankRerankConstMeta => const TaskConstMeta(
+        debugName: "rerank",
+        argNames: ["query", "candidates", "budgetMs", "topN"],
+      );
+
+  @override
+  List<String> crateApiTextSplitterSemanticChunking(
+      {required String modelId, required String text, required int maxChars}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(text, serializer);
+        sse_encode_i_32(maxChars, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 43)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_list_String,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiTextSplitterSemanticChunkingConstMeta,
+      argValues: [modelId, text, maxChars],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiTextSplitterSemanticChunkingConstMeta =>
+      const TaskConstMeta(
+        debugName: "semantic_chunking",
+        argNames: ["modelId", "text", "maxChars"],
+      );
+
+  @override
+  List<String> crateApiTextSplitterSemanticChunkingV2(
+      {required String modelId,
+      required String text,
+      required int minChunkSize}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(text, serializer);
+        sse_encode_i_32(minChunkSize, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 44)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_list_String,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiTextSplitterSemanticChunkingV2ConstMeta,
+      argValues: [modelId, text, minChunkSize],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiTextSplitterSemanticChunkingV2ConstMeta =>
+      const TaskConstMeta(
+        debugName: "semantic_chunking_v2",
+        argNames: ["modelId", "text", "minChunkSize"],
+      );
+
+  @override
+  bool crateApiRustpotionSetEmbeddingTokenBudget(
+      {required String modelId,
+      required int maxTokens,
+      required int? slidingWindowStride}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_i_32(maxTokens, serializer);
+        sse_encode_opt_box_autoadd_i_32(slidingWindowStride, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 45)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRustpotionSetEmbeddingTokenBudgetConstMeta,
+      argValues: [modelId, maxTokens, slidingWindowStride],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionSetEmbeddingTokenBudgetConstMeta =>
+      const TaskConstMeta(
+        debugName: "set_embedding_token_budget",
+        argNames: ["modelId", "maxTokens", "slidingWindowStride"],
+      );
+
+  @override
+  List<String> crateApiTextSplitterSplitText(
+      {required String text, required int maxChars}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(text, serializer);
+        sse_encode_i_32(maxChars, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 46)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_list_String,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiTextSplitterSplitTextConstMeta,
+      argValues: [text, maxChars],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiTextSplitterSplitTextConstMeta =>
+      const TaskConstMeta(
+        debugName: "split_text",
+        argNames: ["text", "maxChars"],
+      );
+
+  @override
+  bool crateApiEmbeddingSwapEmbeddingModel(
+      {required String modelId, required String modelPath}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(modelPath, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 47)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiEmbeddingSwapEmbeddingModelConstMeta,
+      argValues: [modelId, modelPath],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiEmbeddingSwapEmbeddingModelConstMeta =>
+      const TaskConstMeta(
+        debugName: "swap_embedding_model",
+        argNames: ["modelId", "modelPath"],
+      );
+
+  @override
+  bool crateApiRustpotionSwapPotion(
+      {required String modelId, required String modelPath}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        sse_encode_String(modelPath, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 48)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: sse_decode_AnyhowException,
+      ),
+      constMeta: kCrateApiRustpotionSwapPotionConstMeta,
+      argValues: [modelId, modelPath],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionSwapPotionConstMeta =>
+      const TaskConstMeta(
+        debugName: "swap_potion",
+        argNames: ["modelId", "modelPath"],
+      );
+
+  @override
+  bool crateApiEmbeddingUnloadEmbeddingModel({required String modelId}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 49)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: null,
+      ),
+      constMeta: kCrateApiEmbeddingUnloadEmbeddingModelConstMeta,
+      argValues: [modelId],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiEmbeddingUnloadEmbeddingModelConstMeta =>
+      const TaskConstMeta(
+        debugName: "unload_embedding_model",
+        argNames: ["modelId"],
+      );
+
+  @override
+  bool crateApiRustpotionUnloadPotion({required String modelId}) {
+    return handler.executeSync(SyncTask(
+      callFfi: () {
+        final serializer = SseSerializer(generalizedFrbRustBinding);
+        sse_encode_String(modelId, serializer);
+        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 50)!;
+      },
+      codec: SseCodec(
+        decodeSuccessData: sse_decode_bool,
+        decodeErrorData: null,
+      ),
+      constMeta: kCrateApiRustpotionUnloadPotionConstMeta,
+      argValues: [modelId],
+      apiImpl: this,
+    ));
+  }
+
+  TaskConstMeta get kCrateApiRustpotionUnloadPotionConstMeta =>
+      const TaskConstMeta(
+        debugName: "unload_potion",
+        argNames: ["modelId"],
+      );
+
+  @protected
+  AnyhowException dco_decode_AnyhowException(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return AnyhowException(raw as String);
+  }
+
+  @protected
+  String dco_decode_String(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as String;
+  }
+
+  @protected
+  bool dco_decode_bool(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as bool;
+  }
+
+  @protected
+  double dco_decode_box_autoadd_f_64(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as double;
+  }
+
+  @protected
+  int dco_decode_box_autoadd_i_32(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as int;
+  }
+
+  @protected
+  EmbeddingCacheStats dco_decode_embedding_cache_stats(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 4)
+      throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+    return EmbeddingCacheStats(
+      hits: dco_decode_i_64(arr[0]),
+      misses: dco_decode_i_64(arr[1]),
+      entries: dco_decode_i_64(arr[2]),
+      bytes: dco_decode_i_64(arr[3]),
+    );
+  }
+
+  @protected
+  EmbeddingRole dco_decode_embedding_role(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return EmbeddingRole.values[raw as int];
+  }
+
+  @protected
+  double dco_decode_f_64(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as double;
+  }
+
+  @protected
+  HighlightRect dco_decode_highlight_rect(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 4)
+      throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+    return HighlightRect(
+      left: dco_decode_f_64(arr[0]),
+      top: dco_decode_f_64(arr[1]),
+      right: dco_decode_f_64(arr[2]),
+      bottom: dco_decode_f_64(arr[3]),
+    );
+  }
+
+  @protected
+  int dco_decode_i_32(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as int;
+  }
+
+  @protected
+  PlatformInt64 dco_decode_i_64(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dcoDecodeI64(raw);
+  }
+
+  @protected
+  List<String> dco_decode_list_String(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_String).toList();
+  }
+
+  @protected
+  List<HighlightRect> dco_decode_list_highlight_rect(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_highlight_rect).toList();
+  }
+
+  @protected
+  List<Float64List> dco_decode_list_list_prim_f_64_strict(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>)
+        .map(dco_decode_list_prim_f_64_strict)
+        .toList();
+  }
+
+  @protected
+  List<MatchWord> dco_decode_list_match_word(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_match_word).toList();
+  }
+
+  @protected
+  Float64List dco_decode_list_prim_f_64_strict(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as Float64List;
+  }
+
+  @protected
+  Int32List dco_decode_list_prim_i_32_strict(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as Int32List;
+  }
+
+  @protected
+  Int64List dco_decode_list_prim_i_64_strict(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return dcoDecodeInt64List(raw);
+  }
+
+  @protected
+  List<int> dco_decode_list_prim_u_8_loose(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as List<int>;
+  }
+
+  @protected
+  Uint8List dco_decode_list_prim_u_8_strict(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return raw as Uint8List;
+  }
+
+  @protected
+  List<RerankedCandidate> dco_decode_list_reranked_candidate(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_reranked_candidate).toList();
+  }
+
+  @protected
+  List<SimilarWord> dco_decode_list_similar_word(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_similar_word).toList();
+  }
+
+  @protected
+  List<TextWithLocation> dco_decode_list_text_with_location(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    return (raw as List<dynamic>).map(dco_decode_text_with_location).toList();
+  }
+
+  @protected
+  MatchExplanation dco_decode_match_explanation(dynamic raw) {
+    // Codec=Dco (DartCObject based), see doc to use other codecs
+    final arr = raw as List<dynamic>;
+    if (arr.length != 3)
+      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    return MatchExplanation(
+      similarity: dco_decode_f_64(arr[0]),
+      queryWords: dco_decode_list_match_word(arr[1]),
+      textWords: dco_decode_list_match_word(arr[2]),
+    );
   }
 
   @protected
-  int dco_decode_i_32(dynamic raw) {
+  MatchWord dco_decode_match_word(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as int;
+    final arr = raw as List<dynamic>;
+    if (arr.length != 2)
+      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    return MatchWord(
+      word: dco_decode_String(arr[0]),
+      score: dco_decode_f_64(arr[1]),
+    );
   }
 
   @protected
-  PlatformInt64 dco_decode_i_64(dynamic raw) {
+  String? dco_decode_opt_String(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return dcoDecodeI64(raw);
+    return raw == null ? null : dco_decode_String(raw);
   }
 
   @protected
-  List<String> dco_decode_list_String(dynamic raw) {
+  double? dco_decode_opt_box_autoadd_f_64(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return (raw as List<dynamic>).map(dco_decode_String).toList();
+    return raw == null ? null : dco_decode_box_autoadd_f_64(raw);
   }
 
   @protected
-  List<Float64List> dco_decode_list_list_prim_f_64_strict(dynamic raw) {
+  int? dco_decode_opt_box_autoadd_i_32(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return (raw as List<dynamic>)
-        .map(dco_decode_list_prim_f_64_strict)
-        .toList();
+    return raw == null ? null : dco_decode_box_autoadd_i_32(raw);
   }
 
   @protected
-  Float64List dco_decode_list_prim_f_64_strict(dynamic raw) {
+  List<int>? dco_decode_opt_list_prim_u_8_loose(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as Float64List;
+    return raw == null ? null : dco_decode_list_prim_u_8_loose(raw);
   }
 
   @protected
-  Int64List dco_decode_list_prim_i_64_strict(dynamic raw) {
+  PageHighlights dco_decode_page_highlights(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return dcoDecodeInt64List(raw);
+    final arr = raw as List<dynamic>;
+    if (arr.length != 4)
+      throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+    return PageHighlights(
+      pageNumber: dco_decode_i_32(arr[0]),
+      pageWidth: dco_decode_f_64(arr[1]),
+      pageHeight: dco_decode_f_64(arr[2]),
+      rects: dco_decode_list_highlight_rect(arr[3]),
+    );
   }
 
   @protected
-  List<int> dco_decode_list_prim_u_8_loose(dynamic raw) {
+  PdfExtractionReport dco_decode_pdf_extraction_report(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as List<int>;
+    final arr = raw as List<dynamic>;
+    if (arr.length != 5)
+      throw Exception('unexpected arr length: expect 5 but see ${arr.length}');
+    return PdfExtractionReport(
+      pages: dco_decode_list_text_with_location(arr[0]),
+      totalChars: dco_decode_i_64(arr[1]),
+      undecodableChars: dco_decode_i_64(arr[2]),
+      undecodableFraction: dco_decode_f_64(arr[3]),
+      outlinedTextPages: dco_decode_list_prim_i_32_strict(arr[4]),
+    );
   }
 
   @protected
-  Uint8List dco_decode_list_prim_u_8_strict(dynamic raw) {
+  RerankedCandidate dco_decode_reranked_candidate(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as Uint8List;
+    final arr = raw as List<dynamic>;
+    if (arr.length != 2)
+      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    return RerankedCandidate(
+      index: dco_decode_i_32(arr[0]),
+      score: dco_decode_opt_box_autoadd_f_64(arr[1]),
+    );
   }
 
   @protected
-  List<TextWithLocation> dco_decode_list_text_with_location(dynamic raw) {
+  SimilarWord dco_decode_similar_word(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
-    return (raw as List<dynamic>).map(dco_decode_text_with_location).toList();
+    final arr = raw as List<dynamic>;
+    if (arr.length != 2)
+      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    return SimilarWord(
+      word: dco_decode_String(arr[0]),
+      similarity: dco_decode_f_64(arr[1]),
+    );
   }
 
   @protected
@@ -755,12 +1919,57 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return deserializer.buffer.getUint8() != 0;
   }
 
+  @protected
+  double sse_decode_box_autoadd_f_64(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_f_64(deserializer));
+  }
+
+  @protected
+  int sse_decode_box_autoadd_i_32(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    return (sse_decode_i_32(deserializer));
+  }
+
+  @protected
+  EmbeddingCacheStats sse_decode_embedding_cache_stats(
+      SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_hits = sse_decode_i_64(deserializer);
+    var var_misses = sse_decode_i_64(deserializer);
+    var var_entries = sse_decode_i_64(deserializer);
+    var var_bytes = sse_decode_i_64(deserializer);
+    return EmbeddingCacheStats(
+        hits: var_hits,
+        misses: var_misses,
+        entries: var_entries,
+        bytes: var_bytes);
+  }
+
+  @protected
+  EmbeddingRole sse_decode_embedding_role(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var inner = sse_decode_i_32(deserializer);
+    return EmbeddingRole.values[inner];
+  }
+
   @protected
   double sse_decode_f_64(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     return deserializer.buffer.getFloat64();
   }
 
+  @protected
+  HighlightRect sse_decode_highlight_rect(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_left = sse_decode_f_64(deserializer);
+    var var_top = sse_decode_f_64(deserializer);
+    var var_right = sse_decode_f_64(deserializer);
+    var var_bottom = sse_decode_f_64(deserializer);
+    return HighlightRect(
+        left: var_left, top: var_top, right: var_right, bottom: var_bottom);
+  }
+
   @protected
   int sse_decode_i_32(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -785,6 +1994,19 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return ans_;
   }
 
+  @protected
+  List<HighlightRect> sse_decode_list_highlight_rect(
+      SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <HighlightRect>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_highlight_rect(deserializer));
+    }
+    return ans_;
+  }
+
   @protected
   List<Float64List> sse_decode_list_list_prim_f_64_strict(
       SseDeserializer deserializer) {
@@ -798,6 +2020,18 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return ans_;
   }
 
+  @protected
+  List<MatchWord> sse_decode_list_match_word(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <MatchWord>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_match_word(deserializer));
+    }
+    return ans_;
+  }
+
   @protected
   Float64List sse_decode_list_prim_f_64_strict(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -805,6 +2039,13 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return deserializer.buffer.getFloat64List(len_);
   }
 
+  @protected
+  Int32List sse_decode_list_prim_i_32_strict(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var len_ = sse_decode_i_32(deserializer);
+    return deserializer.buffer.getInt32List(len_);
+  }
+
   @protected
   Int64List sse_decode_list_prim_i_64_strict(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -826,6 +2067,31 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return deserializer.buffer.getUint8List(len_);
   }
 
+  @protected
+  List<RerankedCandidate> sse_decode_list_reranked_candidate(
+      SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <RerankedCandidate>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_reranked_candidate(deserializer));
+    }
+    return ans_;
+  }
+
+  @protected
+  List<SimilarWord> sse_decode_list_similar_word(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    var len_ = sse_decode_i_32(deserializer);
+    var ans_ = <SimilarWord>[];
+    for (var idx_ = 0; idx_ < len_; ++idx_) {
+      ans_.add(sse_decode_similar_word(deserializer));
+    }
+    return ans_;
+  }
+
   @protected
   List<TextWithLocation> sse_decode_list_text_with_location(
       SseDeserializer deserializer) {
@@ -839,6 +2105,118 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     return ans_;
   }
 
+  @protected
+  MatchExplanation sse_decode_match_explanation(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_similarity = sse_decode_f_64(deserializer);
+    var var_queryWords = sse_decode_list_match_word(deserializer);
+    var var_textWords = sse_decode_list_match_word(deserializer);
+    return MatchExplanation(
+        similarity: var_similarity,
+        queryWords: var_queryWords,
+        textWords: var_textWords);
+  }
+
+  @protected
+  MatchWord sse_decode_match_word(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_word = sse_decode_String(deserializer);
+    var var_score = sse_decode_f_64(deserializer);
+    return MatchWord(word: var_word, score: var_score);
+  }
+
+  @protected
+  String? sse_decode_opt_String(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_String(deserializer));
+    } else {
+      return null;
+    }
+  }
+
+  @protected
+  double? sse_decode_opt_box_autoadd_f_64(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_box_autoadd_f_64(deserializer));
+    } else {
+      return null;
+    }
+  }
+
+  @protected
+  int? sse_decode_opt_box_autoadd_i_32(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_box_autoadd_i_32(deserializer));
+    } else {
+      return null;
+    }
+  }
+
+  @protected
+  List<int>? sse_decode_opt_list_prim_u_8_loose(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    if (sse_decode_bool(deserializer)) {
+      return (sse_decode_list_prim_u_8_loose(deserializer));
+    } else {
+      return null;
+    }
+  }
+
+  @protected
+  PageHighlights sse_decode_page_highlights(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_pageNumber = sse_decode_i_32(deserializer);
+    var var_pageWidth = sse_decode_f_64(deserializer);
+    var var_pageHeight = sse_decode_f_64(deserializer);
+    var var_rects = sse_decode_list_highlight_rect(deserializer);
+    return PageHighlights(
+        pageNumber: var_pageNumber,
+        pageWidth: var_pageWidth,
+        pageHeight: var_pageHeight,
+        rects: var_rects);
+  }
+
+  @protected
+  PdfExtractionReport sse_decode_pdf_extraction_report(
+      SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_pages = sse_decode_list_text_with_location(deserializer);
+    var var_totalChars = sse_decode_i_64(deserializer);
+    var var_undecodableChars = sse_decode_i_64(deserializer);
+    var var_undecodableFraction = sse_decode_f_64(deserializer);
+    var var_outlinedTextPages = sse_decode_list_prim_i_32_strict(deserializer);
+    return PdfExtractionReport(
+        pages: var_pages,
+        totalChars: var_totalChars,
+        undecodableChars: var_undecodableChars,
+        undecodableFraction: var_undecodableFraction,
+        outlinedTextPages: var_outlinedTextPages);
+  }
+
+  @protected
+  RerankedCandidate sse_decode_reranked_candidate(
+      SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_index = sse_decode_i_32(deserializer);
+    var var_score = sse_decode_opt_box_autoadd_f_64(deserializer);
+    return RerankedCandidate(index: var_index, score: var_score);
+  }
+
+  @protected
+  SimilarWord sse_decode_similar_word(SseDeserializer deserializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    var var_word = sse_decode_String(deserializer);
+    var var_similarity = sse_decode_f_64(deserializer);
+    return SimilarWord(word: var_word, similarity: var_similarity);
+  }
+
   @protected
   TextWithLocation sse_decode_text_with_location(SseDeserializer deserializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -895,12 +2273,49 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     serializer.buffer.putUint8(self ? 1 : 0);
   }
 
+  @protected
+  void sse_encode_box_autoadd_f_64(double self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_f_64(self, serializer);
+  }
+
+  @protected
+  void sse_encode_box_autoadd_i_32(int self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self, serializer);
+  }
+
+  @protected
+  void sse_encode_embedding_cache_stats(
+      EmbeddingCacheStats self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_64(self.hits, serializer);
+    sse_encode_i_64(self.misses, serializer);
+    sse_encode_i_64(self.entries, serializer);
+    sse_encode_i_64(self.bytes, serializer);
+  }
+
+  @protected
+  void sse_encode_embedding_role(EmbeddingRole self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.index, serializer);
+  }
+
   @protected
   void sse_encode_f_64(double self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     serializer.buffer.putFloat64(self);
   }
 
+  @protected
+  void sse_encode_highlight_rect(HighlightRect self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_f_64(self.left, serializer);
+    sse_encode_f_64(self.top, serializer);
+    sse_encode_f_64(self.right, serializer);
+    sse_encode_f_64(self.bottom, serializer);
+  }
+
   @protected
   void sse_encode_i_32(int self, SseSerializer serializer) {
     // Codec=Sse (Serialization based), see doc to use other codecs
@@ -922,6 +2337,16 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     }
   }
 
+  @protected
+  void sse_encode_list_highlight_rect(
+      List<HighlightRect> self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_highlight_rect(item, serializer);
+    }
+  }
+
   @protected
   void sse_encode_list_list_prim_f_64_strict(
       List<Float64List> self, SseSerializer serializer) {
@@ -932,6 +2357,16 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     }
   }
 
+  @protected
+  void sse_encode_list_match_word(
+      List<MatchWord> self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_match_word(item, serializer);
+    }
+  }
+
   @protected
   void sse_encode_list_prim_f_64_strict(
       Float64List self, SseSerializer serializer) {
@@ -940,6 +2375,14 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     serializer.buffer.putFloat64List(self);
   }
 
+  @protected
+  void sse_encode_list_prim_i_32_strict(
+      Int32List self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    serializer.buffer.putInt32List(self);
+  }
+
   @protected
   void sse_encode_list_prim_i_64_strict(
       Int64List self, SseSerializer serializer) {
@@ -965,6 +2408,26 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     serializer.buffer.putUint8List(self);
   }
 
+  @protected
+  void sse_encode_list_reranked_candidate(
+      List<RerankedCandidate> self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_reranked_candidate(item, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_list_similar_word(
+      List<SimilarWord> self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.length, serializer);
+    for (final item in self) {
+      sse_encode_similar_word(item, serializer);
+    }
+  }
+
   @protected
   void sse_encode_list_text_with_location(
       List<TextWithLocation> self, SseSerializer serializer) {
@@ -975,6 +2438,99 @@ class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
     }
   }
 
+  @protected
+  void sse_encode_match_explanation(
+      MatchExplanation self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_f_64(self.similarity, serializer);
+    sse_encode_list_match_word(self.queryWords, serializer);
+    sse_encode_list_match_word(self.textWords, serializer);
+  }
+
+  @protected
+  void sse_encode_match_word(MatchWord self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.word, serializer);
+    sse_encode_f_64(self.score, serializer);
+  }
+
+  @protected
+  void sse_encode_opt_String(String? self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_String(self, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_opt_box_autoadd_f_64(double? self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_box_autoadd_f_64(self, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_opt_box_autoadd_i_32(int? self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_box_autoadd_i_32(self, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_opt_list_prim_u_8_loose(
+      List<int>? self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+
+    sse_encode_bool(self != null, serializer);
+    if (self != null) {
+      sse_encode_list_prim_u_8_loose(self, serializer);
+    }
+  }
+
+  @protected
+  void sse_encode_page_highlights(
+      PageHighlights self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.pageNumber, serializer);
+    sse_encode_f_64(self.pageWidth, serializer);
+    sse_encode_f_64(self.pageHeight, serializer);
+    sse_encode_list_highlight_rect(self.rects, serializer);
+  }
+
+  @protected
+  void sse_encode_pdf_extraction_report(
+      PdfExtractionReport self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_list_text_with_location(self.pages, serializer);
+    sse_encode_i_64(self.totalChars, serializer);
+    sse_encode_i_64(self.undecodableChars, serializer);
+    sse_encode_f_64(self.undecodableFraction, serializer);
+    sse_encode_list_prim_i_32_strict(self.outlinedTextPages, serializer);
+  }
+
+  @protected
+  void sse_encode_reranked_candidate(
+      RerankedCandidate self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_i_32(self.index, serializer);
+    sse_encode_opt_box_autoadd_f_64(self.score, serializer);
+  }
+
+  @protected
+  void sse_encode_similar_word(SimilarWord self, SseSerializer serializer) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    sse_encode_String(self.word, serializer);
+    sse_encode_f_64(self.similarity, serializer);
+  }
+
   @protected
   void sse_encode_text_with_location(
       TextWithLocation self, SseSerializer serializer) {
@@ -6,9 +6,12 @@
 // Static analysis wrongly picks the IO variant, thus ignore this
 // ignore_for_file: argument_type_not_assignable
 
+import 'api/embedding.dart';
 import 'api/fast_html2md_functions.dart';
 import 'api/monolith_functions.dart';
+import 'api/ort_functions.dart';
 import 'api/pdf_text_extractor.dart';
+import 'api/rerank.dart';
 import 'api/rustpotion.dart';
 import 'api/simple.dart';
 import 'api/text_splitter.dart';
@@ -35,9 +38,24 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   bool dco_decode_bool(dynamic raw);
 
+  @protected
+  double dco_decode_box_autoadd_f_64(dynamic raw);
+
+  @protected
+  int dco_decode_box_autoadd_i_32(dynamic raw);
+
+  @protected
+  EmbeddingCacheStats dco_decode_embedding_cache_stats(dynamic raw);
+
+  @protected
+  EmbeddingRole dco_decode_embedding_role(dynamic raw);
+
   @protected
   double dco_decode_f_64(dynamic raw);
 
+  @protected
+  HighlightRect dco_decode_highlight_rect(dynamic raw);
+
   @protected
   int dco_decode_i_32(dynamic raw);
 
@@ -47,12 +65,21 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   List<String> dco_decode_list_String(dynamic raw);
 
+  @protected
+  List<HighlightRect> dco_decode_list_highlight_rect(dynamic raw);
+
   @protected
   List<Float64List> dco_decode_list_list_prim_f_64_strict(dynamic raw);
 
+  @protected
+  List<MatchWord> dco_decode_list_match_word(dynamic raw);
+
   @protected
   Float64List dco_decode_list_prim_f_64_strict(dynamic raw);
 
+  @protected
+  Int32List dco_decode_list_prim_i_32_strict(dynamic raw);
+
   @protected
   Int64List dco_decode_list_prim_i_64_strict(dynamic raw);
 
@@ -62,9 +89,45 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   Uint8List dco_decode_list_prim_u_8_strict(dynamic raw);
 
+  @protected
+  List<RerankedCandidate> dco_decode_list_reranked_candidate(dynamic raw);
+
+  @protected
+  List<SimilarWord> dco_decode_list_similar_word(dynamic raw);
+
   @protected
   List<TextWithLocation> dco_decode_list_text_with_location(dynamic raw);
 
+  @protected
+  MatchExplanation dco_decode_match_explanation(dynamic raw);
+
+  @protected
+  MatchWord dco_decode_match_word(dynamic raw);
+
+  @protected
+  String? dco_decode_opt_String(dynamic raw);
+
+  @protected
+  double? dco_decode_opt_box_autoadd_f_64(dynamic raw);
+
+  @protected
+  int? dco_decode_opt_box_autoadd_i_32(dynamic raw);
+
+  @protected
+  List<int>? dco_decode_opt_list_prim_u_8_loose(dynamic raw);
+
+  @protected
+  PageHighlights dco_decode_page_highlights(dynamic raw);
+
+  @protected
+  PdfExtractionReport dco_decode_pdf_extraction_report(dynamic raw);
+
+  @protected
+  RerankedCandidate dco_decode_reranked_candidate(dynamic raw);
+
+  @protected
+  SimilarWord dco_decode_similar_word(dynamic raw);
+
   @protected
   TextWithLocation dco_decode_text_with_location(dynamic raw);
 
@@ -89,9 +152,25 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   bool sse_decode_bool(SseDeserializer deserializer);
 
+  @protected
+  double sse_decode_box_autoadd_f_64(SseDeserializer deserializer);
+
+  @protected
+  int sse_decode_box_autoadd_i_32(SseDeserializer deserializer);
+
+  @protected
+  EmbeddingCacheStats sse_decode_embedding_cache_stats(
+      SseDeserializer deserializer);
+
+  @protected
+  EmbeddingRole sse_decode_embedding_role(SseDeserializer deserializer);
+
   @protected
   double sse_decode_f_64(SseDeserializer deserializer);
 
+  @protected
+  HighlightRect sse_decode_highlight_rect(SseDeserializer deserializer);
+
   @protected
   int sse_decode_i_32(SseDeserializer deserializer);
 
@@ -101,13 +180,23 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   List<String> sse_decode_list_String(SseDeserializer deserializer);
 
+  @protected
+  List<HighlightRect> sse_decode_list_highlight_rect(
+      SseDeserializer deserializer);
+
   @protected
   List<Float64List> sse_decode_list_list_prim_f_64_strict(
       SseDeserializer deserializer);
 
+  @protected
+  List<MatchWord> sse_decode_list_match_word(SseDeserializer deserializer);
+
   @protected
   Float64List sse_decode_list_prim_f_64_strict(SseDeserializer deserializer);
 
+  @protected
+  Int32List sse_decode_list_prim_i_32_strict(SseDeserializer deserializer);
+
   @protected
   Int64List sse_decode_list_prim_i_64_strict(SseDeserializer deserializer);
 
@@ -117,10 +206,48 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer);
 
+  @protected
+  List<RerankedCandidate> sse_decode_list_reranked_candidate(
+      SseDeserializer deserializer);
+
+  @protected
+  List<SimilarWord> sse_decode_list_similar_word(SseDeserializer deserializer);
+
   @protected
   List<TextWithLocation> sse_decode_list_text_with_location(
       SseDeserializer deserializer);
 
+  @protected
+  MatchExplanation sse_decode_match_explanation(SseDeserializer deserializer);
+
+  @protected
+  MatchWord sse_decode_match_word(SseDeserializer deserializer);
+
+  @protected
+  String? sse_decode_opt_String(SseDeserializer deserializer);
+
+  @protected
+  double? sse_decode_opt_box_autoadd_f_64(SseDeserializer deserializer);
+
+  @protected
+  int? sse_decode_opt_box_autoadd_i_32(SseDeserializer deserializer);
+
+  @protected
+  List<int>? sse_decode_opt_list_prim_u_8_loose(SseDeserializer deserializer);
+
+  @protected
+  PageHighlights sse_decode_page_highlights(SseDeserializer deserializer);
+
+  @protected
+  PdfExtractionReport sse_decode_pdf_extraction_report(
+      SseDeserializer deserializer);
+
+  @protected
+  RerankedCandidate sse_decode_reranked_candidate(SseDeserializer deserializer);
+
+  @protected
+  SimilarWord sse_decode_similar_word(SseDeserializer deserializer);
+
   @protected
   TextWithLocation sse_decode_text_with_location(SseDeserializer deserializer);
 
@@ -146,9 +273,25 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_bool(bool self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_box_autoadd_f_64(double self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_box_autoadd_i_32(int self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_embedding_cache_stats(
+      EmbeddingCacheStats self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_embedding_role(EmbeddingRole self, SseSerializer serializer);
+
   @protected
   void sse_encode_f_64(double self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_highlight_rect(HighlightRect self, SseSerializer serializer);
+
   @protected
   void sse_encode_i_32(int self, SseSerializer serializer);
 
@@ -158,14 +301,26 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   @protected
   void sse_encode_list_String(List<String> self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_list_highlight_rect(
+      List<HighlightRect> self, SseSerializer serializer);
+
   @protected
   void sse_encode_list_list_prim_f_64_strict(
       List<Float64List> self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_list_match_word(
+      List<MatchWord> self, SseSerializer serializer);
+
   @protected
   void sse_encode_list_prim_f_64_strict(
       Float64List self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_list_prim_i_32_strict(
+      Int32List self, SseSerializer serializer);
+
   @protected
   void sse_encode_list_prim_i_64_strict(
       Int64List self, SseSerializer serializer);
@@ -177,10 +332,53 @@ abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
   void sse_encode_list_prim_u_8_strict(
       Uint8List self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_list_reranked_candidate(
+      List<RerankedCandidate> self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_list_similar_word(
+      List<SimilarWord> self, SseSerializer serializer);
+
   @protected
   void sse_encode_list_text_with_location(
       List<TextWithLocation> self, SseSerializer serializer);
 
+  @protected
+  void sse_encode_match_explanation(
+      MatchExplanation self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_match_word(MatchWord self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_String(String? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_box_autoadd_f_64(double? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_box_autoadd_i_32(int? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_opt_list_prim_u_8_loose(
+      List<int>? self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_page_highlights(
+      PageHighlights self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_pdf_extraction_report(
+      PdfExtractionReport self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_reranked_candidate(
+      RerankedCandidate self, SseSerializer serializer);
+
+  @protected
+  void sse_encode_similar_word(SimilarWord self, SseSerializer serializer);
+
   @protected
   void sse_encode_text_with_location(
       TextWithLocation self, SseSerializer serializer);
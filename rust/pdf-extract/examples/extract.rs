@@ -13,6 +13,8 @@ fn main() {
     //let output_kind = "html";
     //let output_kind = "txt";
     //let output_kind = "svg";
+    //let output_kind = "json";
+    //let output_kind = "hocr";
     let file = env::args().nth(1).unwrap();
     let output_kind = env::args().nth(2).unwrap_or_else(|| "txt".to_owned());
     println!("{}", file);
@@ -31,6 +33,8 @@ fn main() {
         "txt" => Box::new(PlainTextOutput::new(&mut output_file as &mut dyn std::io::Write)),
         "html" => Box::new(HTMLOutput::new(&mut output_file)),
         "svg" => Box::new(SVGOutput::new(&mut output_file)),
+        "json" => Box::new(JSONOutput::new(&mut output_file)),
+        "hocr" => Box::new(HOCROutput::new(&mut output_file)),
         _ => panic!(),
     };
 
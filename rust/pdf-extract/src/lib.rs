@@ -12,7 +12,6 @@ extern crate euclid;
 extern crate adobe_cmap_parser;
 extern crate type1_encoding_parser;
 extern crate unicode_normalization;
-use euclid::vec2;
 use unicode_normalization::UnicodeNormalization;
 use std::fmt;
 use std::str;
@@ -307,6 +306,7 @@ struct PdfSimpleFont<'a> {
     unicode_map: Option<HashMap<u32, String>>,
     widths: HashMap<CharCode, f64>, // should probably just use i32 here
     missing_width: f64,
+    symbolic: bool,
 }
 
 #[derive(Clone)]
@@ -316,6 +316,7 @@ struct PdfType3Font<'a> {
     encoding: Option<Vec<u16>>,
     unicode_map: Option<HashMap<u32, String>>,
     widths: HashMap<CharCode, f64>, // should probably just use i32 here
+    symbolic: bool,
 }
 
 
@@ -364,6 +365,79 @@ fn encoding_to_unicode_table(name: &[u8]) -> Vec<u16> {
     encoding_table
 }
 
+/* Recover the text of a glyph name that isn't in the glyph list using the rules from the
+   Adobe Glyph List specification: "uniXXXX" sequences, "uXXXX[XX]", suffixes after a period
+   ("a.sc") and ligatures joined with underscores ("f_f_i"). Symbolic fonts also get the
+   ZapfDingbats names ("a12") which would otherwise clash with generated names like Type3 "a65" */
+fn glyph_name_to_string(name: &str, symbolic: bool) -> Option<String> {
+    let name = name.split('.').next().unwrap();
+    if name.is_empty() {
+        return None;
+    }
+    let mut result = String::new();
+    for component in name.split('_') {
+        let from_hex = |hex: &str| u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+        if let Some(c) = glyphnames::name_to_unicode(component) {
+            result.push(char::from_u32(c as u32)?);
+        } else if let Some(zapf) = zapfglyphnames::zapfdigbats_names_to_unicode(component).filter(|_| symbolic) {
+            result.push(char::from_u32(zapf as u32)?);
+        } else if let Some(hex) = component.strip_prefix("uni").filter(|hex| !hex.is_empty()) {
+            for chunk in hex.as_bytes().chunks(4) {
+                if chunk.len() != 4 {
+                    return None;
+                }
+                result.push(from_hex(str::from_utf8(chunk).ok()?)?);
+            }
+        } else if let Some(hex) = component.strip_prefix('u').filter(|hex| (4..=6).contains(&hex.len())) {
+            result.push(from_hex(hex)?);
+        } else {
+            return None;
+        }
+    }
+    Some(result)
+}
+
+// Like `glyphnames::name_to_unicode` but falling back to `glyph_name_to_string` for names that
+// still fit in a single UTF-16 code unit
+fn glyph_name_to_unicode(name: &str, symbolic: bool) -> Option<u16> {
+    glyphnames::name_to_unicode(name).or_else(|| {
+        let s = glyph_name_to_string(name, symbolic)?;
+        let mut units = s.encode_utf16();
+        match (units.next(), units.next()) {
+            (Some(u), None) => Some(u),
+            _ => None
+        }
+    })
+}
+
+// "Symbolic: Font contains glyphs outside the Adobe standard Latin character set"
+fn is_symbolic(doc: &Document, font: &Dictionary) -> bool {
+    let descriptor: Option<&Dictionary> = get(doc, font, b"FontDescriptor");
+    descriptor.and_then(|d| maybe_get::<i64>(doc, d, b"Flags")).is_some_and(|flags| flags & 4 != 0)
+}
+
+/* Symbolic TrueType fonts are usually addressed through the (3, 0) cmap which places the glyphs
+   at 0xF000 + code, and producers often copy those private use code points into ToUnicode.
+   Like pdf.js we map them back onto the single byte range. */
+fn unmap_symbol_range(s: String) -> String {
+    if s.chars().any(|c| ('\u{f020}'..='\u{f0ff}').contains(&c)) {
+        s.chars().map(|c| if ('\u{f020}'..='\u{f0ff}').contains(&c) { char::from_u32(c as u32 - 0xf000).unwrap() } else { c }).collect()
+    } else {
+        s
+    }
+}
+
+/* Whether decoded text is junk rather than something worth indexing: nothing at all, control
+   characters, private use code points or replacement characters */
+fn is_undecodable(s: &str) -> bool {
+    s.chars().all(|c| {
+        (c.is_control() && !c.is_whitespace()) ||
+        ('\u{e000}'..='\u{f8ff}').contains(&c) ||
+        c >= '\u{f0000}' ||
+        c == char::REPLACEMENT_CHARACTER
+    })
+}
+
 /* "Glyphs in the font are selected by single-byte character codes obtained from a string that
     is shown by the text-showing operators. Logically, these codes index into a table of 256
     glyphs; the mapping from codes to glyphs is called the font's encoding. Each font program
@@ -378,6 +452,7 @@ impl<'a> PdfSimpleFont<'a> {
         let encoding: Option<&Object> = get(doc, font, b"Encoding");
         dlog!("base_name {} {} enc:{:?} {:?}", base_name, subtype, encoding, font);
         let descriptor: Option<&Dictionary> = get(doc, font, b"FontDescriptor");
+        let symbolic = is_symbolic(doc, font) || base_name == "Symbol" || base_name == "ZapfDingbats";
         let mut type1_encoding = None;
         if let Some(descriptor) = descriptor {
             dlog!("descriptor {:?}", descriptor);
@@ -448,7 +523,7 @@ impl<'a> PdfSimpleFont<'a> {
                                 let name = pdf_to_utf8(&n);
                                 // XXX: names of Type1 fonts can map to arbitrary strings instead of real
                                 // unicode names, so we should probably handle this differently
-                                let unicode = glyphnames::name_to_unicode(&name);
+                                let unicode = glyph_name_to_unicode(&name, symbolic);
                                 if let Some(unicode) = unicode{
                                     table[code as usize] = unicode;
                                     if let Some(ref mut unicode_map) = unicode_map {
@@ -506,7 +581,7 @@ impl<'a> PdfSimpleFont<'a> {
                     let mut table = Vec::from(PDFDocEncoding);
                     dlog!("type1encoding");
                     for (code, name) in type1_encoding {
-                        let unicode = glyphnames::name_to_unicode(&pdf_to_utf8(&name));
+                        let unicode = glyph_name_to_unicode(&pdf_to_utf8(&name), symbolic);
                         if let Some(unicode) = unicode {
                             table[code as usize] = unicode;
                         } else {
@@ -615,7 +690,7 @@ impl<'a> PdfSimpleFont<'a> {
         }
 
         let missing_width = get::<Option<f64>>(doc, font, b"MissingWidth").unwrap_or(0.);
-        PdfSimpleFont {doc, font, widths: width_map, encoding: encoding_table, missing_width, unicode_map}
+        PdfSimpleFont {doc, font, widths: width_map, encoding: encoding_table, missing_width, unicode_map, symbolic}
     }
 
     #[allow(dead_code)]
@@ -654,6 +729,7 @@ impl<'a> PdfType3Font<'a> {
 
         let unicode_map = get_unicode_map(doc, font);
         let encoding: Option<&Object> = get(doc, font, b"Encoding");
+        let symbolic = is_symbolic(doc, font);
 
         let encoding_table;
         match encoding {
@@ -680,7 +756,7 @@ impl<'a> PdfType3Font<'a> {
                                 let name = pdf_to_utf8(&n);
                                 // XXX: names of Type1 fonts can map to arbitrary strings instead of real
                                 // unicode names, so we should probably handle this differently
-                                let unicode = glyphnames::name_to_unicode(&name);
+                                let unicode = glyph_name_to_unicode(&name, symbolic);
                                 if let Some(unicode) = unicode{
                                     table[code as usize] = unicode;
                                 }
@@ -720,7 +796,7 @@ impl<'a> PdfType3Font<'a> {
             i += 1;
         }
         assert_eq!(first_char + i - 1, last_char);
-        PdfType3Font {doc, font, widths: width_map, encoding: encoding_table, unicode_map}
+        PdfType3Font {doc, font, widths: width_map, encoding: encoding_table, unicode_map, symbolic}
     }
 }
 
@@ -743,6 +819,7 @@ trait PdfFont : Debug {
     fn get_width(&self, id: CharCode) -> f64;
     fn next_char(&self, iter: &mut Iter<u8>) -> Option<(CharCode, u8)>;
     fn decode_char(&self, char: CharCode) -> String;
+    fn name(&self) -> String;
 
         /*fn char_codes<'a>(&'a self, chars: &'a [u8]) -> PdfFontIter {
             let p = self;
@@ -764,6 +841,9 @@ impl<'a> dyn PdfFont + 'a {
 
 
 impl<'a> PdfFont for PdfSimpleFont<'a> {
+    fn name(&self) -> String {
+        maybe_get_name_string(self.doc, self.font, b"BaseFont").unwrap_or_default()
+    }
     fn get_width(&self, id: CharCode) -> f64 {
         let width = self.widths.get(&id);
         if let Some(width) = width {
@@ -799,12 +879,12 @@ impl<'a> PdfFont for PdfSimpleFont<'a> {
                 }
                 Some(s) => { s.clone() }
             };
-            return s
+            return if self.symbolic { unmap_symbol_range(s) } else { s }
         }
         let encoding = self.encoding.as_ref().map(|x| &x[..]).unwrap_or(&PDFDocEncoding);
         //dlog!("char_code {:?} {:?}", char, self.encoding);
         let s = to_utf8(encoding, &slice);
-        s
+        if self.symbolic { unmap_symbol_range(s) } else { s }
     }
 }
 
@@ -817,6 +897,10 @@ impl<'a> fmt::Debug for PdfSimpleFont<'a> {
 }
 
 impl<'a> PdfFont for PdfType3Font<'a> {
+    fn name(&self) -> String {
+        // Type3 fonts have no BaseFont, only an optional (obsolescent) Name
+        maybe_get_name_string(self.doc, self.font, b"Name").unwrap_or_else(|| "Type3".to_owned())
+    }
     fn get_width(&self, id: CharCode) -> f64 {
         let width = self.widths.get(&id);
         if let Some(width) = width {
@@ -849,12 +933,12 @@ impl<'a> PdfFont for PdfType3Font<'a> {
                 }
                 Some(s) => { s.clone() }
             };
-            return s
+            return if self.symbolic { unmap_symbol_range(s) } else { s }
         }
         let encoding = self.encoding.as_ref().map(|x| &x[..]).unwrap_or(&PDFDocEncoding);
         //dlog!("char_code {:?} {:?}", char, self.encoding);
         let s = to_utf8(encoding, &slice);
-        s
+        if self.symbolic { unmap_symbol_range(s) } else { s }
     }
 }
 
@@ -998,6 +1082,9 @@ impl<'a> PdfCIDFont<'a> {
 }
 
 impl<'a> PdfFont for PdfCIDFont<'a> {
+    fn name(&self) -> String {
+        maybe_get_name_string(self.doc, self.font, b"BaseFont").unwrap_or_default()
+    }
     fn get_width(&self, id: CharCode) -> f64 {
         let width = self.widths.get(&id);
         if let Some(width) = width {
@@ -1236,6 +1323,7 @@ struct GraphicsState<'a>
 fn show_text(gs: &mut GraphicsState, s: &[u8],
              _tlm: &Transform,
              _flip_ctm: &Transform,
+             stats: &mut DecodeStats,
              output: &mut dyn OutputDev) -> Result<(), OutputError> {
     let ts = &mut gs.ts;
     let font = ts.font.as_ref().unwrap();
@@ -1243,6 +1331,7 @@ fn show_text(gs: &mut GraphicsState, s: &[u8],
     dlog!("{:?}", font.decode(s));
     dlog!("{:?}", font.decode(s).as_bytes());
     dlog!("{:?}", s);
+    output.set_font(&font.name())?;
     output.begin_word()?;
 
     for (c, length) in font.char_codes(s) {
@@ -1271,7 +1360,15 @@ fn show_text(gs: &mut GraphicsState, s: &[u8],
         let is_space = c == 32 && length == 1;
         if is_space { spacing += ts.word_spacing }
 
-        output.output_character(&trm, w0, spacing, ts.font_size, &font.decode_char(c))?;
+        // Glyphs that don't decode to real text are passed on as a replacement character
+        // so that output devices can flag them instead of emitting junk
+        let mut text = font.decode_char(c);
+        stats.total_chars += 1;
+        if is_undecodable(&text) {
+            stats.undecodable_chars += 1;
+            text = char::REPLACEMENT_CHARACTER.to_string();
+        }
+        output.output_character(&trm, w0, spacing, ts.font_size, &text)?;
         let tj = 0.;
         let ty = 0.;
         let tx = ts.horizontal_scaling * ((w0 - tj/1000.)* ts.font_size + spacing);
@@ -1294,6 +1391,76 @@ pub struct MediaBox {
     pub ury: f64
 }
 
+impl MediaBox {
+    // Page boxes are allowed to list any two opposite corners, so normalize them here
+    fn from_array(r: &[f64]) -> MediaBox {
+        MediaBox { llx: r[0].min(r[2]), lly: r[1].min(r[3]), urx: r[0].max(r[2]), ury: r[1].max(r[3]) }
+    }
+    fn intersect(&self, other: &MediaBox) -> MediaBox {
+        MediaBox { llx: self.llx.max(other.llx), lly: self.lly.max(other.lly), urx: self.urx.min(other.urx), ury: self.ury.min(other.ury) }
+    }
+    pub fn width(&self) -> f64 {
+        self.urx - self.llx
+    }
+    pub fn height(&self) -> f64 {
+        self.ury - self.lly
+    }
+}
+
+/// The visible area of a page: the CropBox (clipped to the MediaBox) shown
+/// rotated clockwise by `rotate` degrees, as a viewer would display it.
+#[derive(Debug, Clone, Copy)]
+pub struct PageGeometry {
+    pub media_box: MediaBox,
+    pub crop_box: MediaBox,
+    /// One of 0, 90, 180 or 270
+    pub rotate: u32,
+}
+
+impl PageGeometry {
+    pub fn new(media_box: MediaBox, crop_box: Option<MediaBox>, rotate: i64) -> PageGeometry {
+        let crop_box = crop_box.map(|c| c.intersect(&media_box)).unwrap_or(media_box);
+        // "The value must be a multiple of 90." Anything else is treated as unrotated
+        let rotate = match rotate.rem_euclid(360) {
+            r @ (90 | 180 | 270) => r as u32,
+            _ => 0,
+        };
+        PageGeometry { media_box, crop_box, rotate }
+    }
+
+    fn is_sideways(&self) -> bool {
+        self.rotate == 90 || self.rotate == 270
+    }
+
+    /// Width of the page as displayed
+    pub fn width(&self) -> f64 {
+        if self.is_sideways() { self.crop_box.height() } else { self.crop_box.width() }
+    }
+
+    /// Height of the page as displayed
+    pub fn height(&self) -> f64 {
+        if self.is_sideways() { self.crop_box.width() } else { self.crop_box.height() }
+    }
+
+    /// Maps PDF user space to device space: origin at the top-left corner of the
+    /// displayed (cropped and rotated) page with y growing downwards.
+    pub fn device_transform(&self) -> Transform {
+        let MediaBox { llx, lly, urx, ury } = self.crop_box;
+        match self.rotate {
+            90 => Transform2D::row_major(0., 1., 1., 0., -lly, -llx),
+            180 => Transform2D::row_major(-1., 0., 0., 1., urx, -lly),
+            270 => Transform2D::row_major(0., -1., -1., 0., ury, urx),
+            _ => Transform2D::row_major(1., 0., 0., -1., -llx, ury),
+        }
+    }
+}
+
+// The size of `font_size` once scaled by `trm`. We use the determinant so that rotated
+// and mirrored text matrices give the same answer as upright ones.
+fn transformed_font_size(trm: &Transform, font_size: f64) -> f64 {
+    (trm.m11 * trm.m22 - trm.m12 * trm.m21).abs().sqrt() * font_size
+}
+
 fn apply_state(doc: &Document, gs: &mut GraphicsState, state: &Dictionary) {
     for (k, v) in state.iter() {
         let k : &[u8] = k.as_ref();
@@ -1529,15 +1696,17 @@ fn make_colorspace<'a>(doc: &'a Document, name: &[u8], resources: &'a Dictionary
 }
 
 struct Processor<'a> {
+    stats: DecodeStats,
+    paint: PaintStats,
     _none: PhantomData<&'a ()>
 }
 
 impl<'a> Processor<'a> {
     fn new() -> Processor<'a> {
-        Processor { _none: PhantomData }
+        Processor { stats: DecodeStats::default(), paint: PaintStats::default(), _none: PhantomData }
     }
 
-    fn process_stream(&mut self, doc: &'a Document, content: Vec<u8>, resources: &'a Dictionary, media_box: &MediaBox, output: &mut dyn OutputDev, page_num: u32) -> Result<(), OutputError> {
+    fn process_stream(&mut self, doc: &'a Document, content: Vec<u8>, resources: &'a Dictionary, page: &PageGeometry, output: &mut dyn OutputDev, page_num: u32) -> Result<(), OutputError> {
         let content = Content::decode(&content).unwrap();
         let mut font_table = HashMap::new();
         let mut gs: GraphicsState = GraphicsState {
@@ -1565,8 +1734,8 @@ impl<'a> Processor<'a> {
         // XXX: replace tlm with a point for text start
         let mut tlm = Transform2D::identity();
         let mut path = Path::new();
-        let flip_ctm = Transform2D::row_major(1., 0., 0., -1., 0., media_box.ury - media_box.lly);
-        dlog!("MediaBox {:?}", media_box);
+        let flip_ctm = page.device_transform();
+        dlog!("PageGeometry {:?}", page);
         for operation in &content.operations {
             //dlog!("op: {:?}", operation);
 
@@ -1614,12 +1783,13 @@ impl<'a> Processor<'a> {
                     dlog!("unhandled color operation {:?}", operation);
                 }
                 "TJ" => {
+                    self.paint.text_operators += 1;
                     match operation.operands[0] {
                         Object::Array(ref array) => {
                             for e in array {
                                 match e {
                                     &Object::String(ref s, _) => {
-                                        show_text(&mut gs, s, &tlm, &flip_ctm, output)?;
+                                        show_text(&mut gs, s, &tlm, &flip_ctm, &mut self.stats, output)?;
                                     }
                                     &Object::Integer(i) => {
                                         let ts = &mut gs.ts;
@@ -1647,9 +1817,10 @@ impl<'a> Processor<'a> {
                     }
                 }
                 "Tj" => {
+                    self.paint.text_operators += 1;
                     match operation.operands[0] {
                         Object::String(ref s, _) => {
-                            show_text(&mut gs, s, &tlm, &flip_ctm, output)?;
+                            show_text(&mut gs, s, &tlm, &flip_ctm, &mut self.stats, output)?;
                         }
                         _ => { panic!("unexpected Tj operand {:?}", operation) }
                     }
@@ -1797,12 +1968,21 @@ impl<'a> Processor<'a> {
                 }
                 "s" | "f*" | "B" | "B*" | "b" => {
                     dlog!("unhandled path op {:?}", operation);
+                    // still count them: glyph outlines are usually filled with the even-odd rule
+                    if operation.operator != "s" {
+                        self.paint.filled_paths += 1;
+                        self.paint.filled_segments += path.ops.len();
+                    }
+                    path.ops.clear();
                 }
                 "S" => {
+                    self.paint.stroked_segments += path.ops.len();
                     output.stroke(&gs.ctm, &gs.stroke_colorspace, &gs.stroke_color, &path)?;
                     path.ops.clear();
                 }
                 "F" | "f" => {
+                    self.paint.filled_paths += 1;
+                    self.paint.filled_segments += path.ops.len();
                     output.fill(&gs.ctm, &gs.fill_colorspace, &gs.fill_color, &path)?;
                     path.ops.clear();
                 }
@@ -1825,7 +2005,7 @@ impl<'a> Processor<'a> {
                     let xf: &Stream = get(&doc, xobject, name);
                     let resources = maybe_get_obj(&doc, &xf.dict, b"Resources").and_then(|n| n.as_dict().ok()).unwrap_or(resources);
                     let contents = get_contents(xf);
-                    self.process_stream(&doc, contents, resources, &media_box, output, page_num)?;
+                    self.process_stream(&doc, contents, resources, page, output, page_num)?;
                 }
                 _ => { dlog!("unknown operation {:?}", operation); }
 
@@ -1837,7 +2017,7 @@ impl<'a> Processor<'a> {
 
 
 pub trait OutputDev {
-    fn begin_page(&mut self, page_num: u32, media_box: &MediaBox, art_box: Option<(f64, f64, f64, f64)>)-> Result<(), OutputError>;
+    fn begin_page(&mut self, page_num: u32, page: &PageGeometry, art_box: Option<(f64, f64, f64, f64)>)-> Result<(), OutputError>;
     fn end_page(&mut self)-> Result<(), OutputError>;
     fn output_character(&mut self, trm: &Transform, width: f64, spacing: f64, font_size: f64, char: &str) -> Result<(), OutputError>;
     fn begin_word(&mut self)-> Result<(), OutputError>;
@@ -1845,6 +2025,10 @@ pub trait OutputDev {
     fn end_line(&mut self)-> Result<(), OutputError>;
     fn stroke(&mut self, _ctm: &Transform, _colorspace: &ColorSpace, _color: &[f64], _path: &Path)-> Result<(), OutputError> {Ok(())}
     fn fill(&mut self, _ctm: &Transform, _colorspace: &ColorSpace, _color: &[f64], _path: &Path)-> Result<(), OutputError> {Ok(())}
+    /// Called before each run of characters with the BaseFont of the font they are drawn in
+    fn set_font(&mut self, _font_name: &str)-> Result<(), OutputError> {Ok(())}
+    /// Called by `output_doc` after the last page
+    fn end_document(&mut self)-> Result<(), OutputError> {Ok(())}
 }
 
 
@@ -1892,9 +2076,7 @@ impl<'a> HTMLOutput<'a> {
         if self.buf.len() != 0 {
 
             let position = self.buf_ctm.post_transform(&self.flip_ctm);
-            let transformed_font_size_vec = self.buf_ctm.transform_vector(vec2(self.buf_font_size, self.buf_font_size));
-            // get the length of one sized of the square with the same area with a rectangle of size (x, y)
-            let transformed_font_size = (transformed_font_size_vec.x * transformed_font_size_vec.y).sqrt();
+            let transformed_font_size = transformed_font_size(&self.buf_ctm, self.buf_font_size);
             let (x, y) = (position.m31, position.m32);
             println!("flush {} {:?}", self.buf, (x,y));
 
@@ -1908,11 +2090,11 @@ impl<'a> HTMLOutput<'a> {
 type ArtBox = (f64, f64, f64, f64);
 
 impl<'a> OutputDev for HTMLOutput<'a> {
-    fn begin_page(&mut self, page_num: u32, media_box: &MediaBox, _: Option<ArtBox>) -> Result<(), OutputError> {
+    fn begin_page(&mut self, page_num: u32, page: &PageGeometry, _: Option<ArtBox>) -> Result<(), OutputError> {
         write!(self.file, "<meta charset='utf-8' /> ")?;
         write!(self.file, "<!-- page {} -->", page_num)?;
-        write!(self.file, "<div id='page{}' style='position: relative; height: {}px; width: {}px; border: 1px black solid'>", page_num, media_box.ury - media_box.lly, media_box.urx - media_box.llx)?;
-        self.flip_ctm = Transform::row_major(1., 0., 0., -1., 0., media_box.ury - media_box.lly);
+        write!(self.file, "<div id='page{}' style='position: relative; height: {}px; width: {}px; border: 1px black solid'>", page_num, page.height(), page.width())?;
+        self.flip_ctm = page.device_transform();
         Ok(())
     }
     fn end_page(&mut self) -> Result<(), OutputError> {
@@ -1937,9 +2119,7 @@ impl<'a> OutputDev for HTMLOutput<'a> {
             self.buf_ctm = *trm;
         }
         let position = trm.post_transform(&self.flip_ctm);
-        let transformed_font_size_vec = trm.transform_vector(vec2(font_size, font_size));
-        // get the length of one sized of the square with the same area with a rectangle of size (x, y)
-        let transformed_font_size = (transformed_font_size_vec.x * transformed_font_size_vec.y).sqrt();
+        let transformed_font_size = transformed_font_size(trm, font_size);
         let (x, y) = (position.m31, position.m32);
         write!(self.file, "<div style='position: absolute; color: red; left: {}px; top: {}px; font-size: {}px'>{}</div>",
                x, y, transformed_font_size, char)?;
@@ -1962,7 +2142,7 @@ impl<'a> SVGOutput<'a> {
 }
 
 impl<'a> OutputDev for SVGOutput<'a> {
-    fn begin_page(&mut self, _page_num: u32, media_box: &MediaBox, art_box: Option<(f64, f64, f64, f64)>) -> Result<(), OutputError> {
+    fn begin_page(&mut self, _page_num: u32, page: &PageGeometry, art_box: Option<(f64, f64, f64, f64)>) -> Result<(), OutputError> {
         let ver = 1.1;
         write!(self.file, "<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n")?;
         if ver == 1.1 {
@@ -1970,20 +2150,21 @@ impl<'a> OutputDev for SVGOutput<'a> {
         } else {
             write!(self.file, r#"<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.0//EN" "http://www.w3.org/TR/2001/REC-SVG-20010904/DTD/svg10.dtd">"#)?;
         }
+        let ctm = page.device_transform();
         if let Some(art_box) = art_box {
-            let width = art_box.2 - art_box.0;
-            let height = art_box.3 - art_box.1;
-            let y = media_box.ury - art_box.1 - height;
-            write!(self.file, "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\" version=\"{}\" viewBox='{} {} {} {}'>", width, height, ver, art_box.0, y, width, height)?;
+            // The ArtBox is in user space so find where its corners land on the displayed page
+            let a = ctm.transform_point(point2(art_box.0, art_box.1));
+            let b = ctm.transform_point(point2(art_box.2, art_box.3));
+            let width = (b.x - a.x).abs();
+            let height = (b.y - a.y).abs();
+            write!(self.file, "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\" version=\"{}\" viewBox='{} {} {} {}'>", width, height, ver, a.x.min(b.x), a.y.min(b.y), width, height)?;
         } else {
-            let width = media_box.urx - media_box.llx;
-            let height = media_box.ury - media_box.lly;
-            write!(self.file, "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\" version=\"{}\" viewBox='{} {} {} {}'>", width, height, ver, media_box.llx, media_box.lly, width, height)?;
+            let width = page.width();
+            let height = page.height();
+            write!(self.file, "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\" version=\"{}\" viewBox='0 0 {} {}'>", width, height, ver, width, height)?;
         }
         write!(self.file, "\n")?;
-        type Mat = Transform;
 
-        let ctm = Mat::create_scale(1., -1.).post_translate(vec2(0., media_box.ury));
         write!(self.file, "<g transform='matrix({}, {}, {}, {}, {}, {})'>\n",
                ctm.m11,
                ctm.m12,
@@ -2094,6 +2275,7 @@ pub struct PlainTextOutput<W: ConvertToFmt>   {
     last_y: f64,
     first_char: bool,
     flip_ctm: Transform,
+    in_undecodable_run: bool,
 }
 
 impl<W: ConvertToFmt> PlainTextOutput<W> {
@@ -2104,6 +2286,7 @@ impl<W: ConvertToFmt> PlainTextOutput<W> {
             first_char: false,
             last_y: 0.,
             flip_ctm: Transform2D::identity(),
+            in_undecodable_run: false,
         }
     }
 }
@@ -2111,8 +2294,8 @@ impl<W: ConvertToFmt> PlainTextOutput<W> {
 /* There are some structural hints that PDFs can use to signal word and line endings:
  * however relying on these is not likely to be sufficient. */
 impl<W: ConvertToFmt> OutputDev for PlainTextOutput<W> {
-    fn begin_page(&mut self, _page_num: u32, media_box: &MediaBox, _: Option<ArtBox>) -> Result<(), OutputError> {
-        self.flip_ctm = Transform2D::row_major(1., 0., 0., -1., 0., media_box.ury - media_box.lly);
+    fn begin_page(&mut self, _page_num: u32, page: &PageGeometry, _: Option<ArtBox>) -> Result<(), OutputError> {
+        self.flip_ctm = page.device_transform();
         Ok(())
     }
     fn end_page(&mut self) -> Result<(), OutputError> {
@@ -2120,29 +2303,35 @@ impl<W: ConvertToFmt> OutputDev for PlainTextOutput<W> {
     }
     fn output_character(&mut self, trm: &Transform, width: f64, _spacing: f64, font_size: f64, char: &str) -> Result<(), OutputError> {
         let position = trm.post_transform(&self.flip_ctm);
-        let transformed_font_size_vec = trm.transform_vector(vec2(font_size, font_size));
-        // get the length of one sized of the square with the same area with a rectangle of size (x, y)
-        let transformed_font_size = (transformed_font_size_vec.x*transformed_font_size_vec.y).sqrt();
+        let transformed_font_size = transformed_font_size(trm, font_size);
         let (x, y) = (position.m31, position.m32);
         use std::fmt::Write;
         //dlog!("last_end: {} x: {}, width: {}", self.last_end, x, width);
         if self.first_char {
             if (y - self.last_y).abs() > transformed_font_size * 1.5 {
                 write!(self.writer, "\n")?;
+                self.in_undecodable_run = false;
             }
 
             // we've moved to the left and down
             if x < self.last_end && (y - self.last_y).abs() > transformed_font_size * 0.5 {
                 write!(self.writer, "\n")?;
+                self.in_undecodable_run = false;
             }
 
             if x > self.last_end + transformed_font_size * 0.1 {
                 dlog!("width: {}, space: {}, thresh: {}", width, x - self.last_end, transformed_font_size * 0.1);
                 write!(self.writer, " ")?;
+                self.in_undecodable_run = false;
             }
         }
         //let norm = unicode_normalization::UnicodeNormalization::nfkc(char);
-        write!(self.writer, "{}", char)?;
+        // A run of undecodable glyphs is flagged with a single replacement character
+        let undecodable = char == "\u{fffd}";
+        if !(undecodable && self.in_undecodable_run) {
+            write!(self.writer, "{}", char)?;
+        }
+        self.in_undecodable_run = undecodable;
         self.first_char = false;
         self.last_y = y;
         self.last_end = x + width * transformed_font_size;
@@ -2160,6 +2349,434 @@ impl<W: ConvertToFmt> OutputDev for PlainTextOutput<W> {
 }
 
 
+/// An axis aligned box in device space (origin at the top left of the displayed page, y pointing down)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BBox {
+    pub x0: f64,
+    pub y0: f64,
+    pub x1: f64,
+    pub y1: f64,
+}
+
+impl BBox {
+    fn union(&mut self, other: &BBox) {
+        self.x0 = self.x0.min(other.x0);
+        self.y0 = self.y0.min(other.y0);
+        self.x1 = self.x1.max(other.x1);
+        self.y1 = self.y1.max(other.y1);
+    }
+    pub fn width(&self) -> f64 {
+        self.x1 - self.x0
+    }
+    pub fn height(&self) -> f64 {
+        self.y1 - self.y0
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LayoutWord {
+    pub text: String,
+    pub bbox: BBox,
+    /// BaseFont of the first character of the word
+    pub font: String,
+    /// Font size after the text and page transforms have been applied
+    pub font_size: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct LayoutLine {
+    pub bbox: BBox,
+    pub words: Vec<LayoutWord>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LayoutBlock {
+    pub bbox: BBox,
+    pub lines: Vec<LayoutLine>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LayoutPage {
+    pub page_num: u32,
+    pub width: f64,
+    pub height: f64,
+    pub blocks: Vec<LayoutBlock>,
+}
+
+impl LayoutPage {
+    pub fn words(&self) -> impl Iterator<Item = &LayoutWord> {
+        self.blocks.iter().flat_map(|b| b.lines.iter()).flat_map(|l| l.words.iter())
+    }
+
+    /// Serializes the page as a JSON object:
+    /// `{"page": n, "width": w, "height": h, "blocks": [{"bbox": [x0, y0, x1, y1], "lines": [{"bbox": .., "words": [{"text": .., "bbox": .., "font": .., "font_size": ..}]}]}]}`
+    pub fn to_json(&self) -> String {
+        let mut s = String::new();
+        self.write_json(&mut s).unwrap();
+        s
+    }
+
+    fn write_json(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        write!(w, "{{\"page\":{},\"width\":{},\"height\":{},\"blocks\":[", self.page_num, json_number(self.width), json_number(self.height))?;
+        for (i, block) in self.blocks.iter().enumerate() {
+            if i > 0 { write!(w, ",")?; }
+            write!(w, "{{\"bbox\":{},\"lines\":[", json_bbox(&block.bbox))?;
+            for (j, line) in block.lines.iter().enumerate() {
+                if j > 0 { write!(w, ",")?; }
+                write!(w, "{{\"bbox\":{},\"words\":[", json_bbox(&line.bbox))?;
+                for (k, word) in line.words.iter().enumerate() {
+                    if k > 0 { write!(w, ",")?; }
+                    write!(w, "{{\"text\":\"{}\",\"bbox\":{},\"font\":\"{}\",\"font_size\":{}}}",
+                           json_escape(&word.text), json_bbox(&word.bbox), json_escape(&word.font), json_number(word.font_size))?;
+                }
+                write!(w, "]}}")?;
+            }
+            write!(w, "]}}")?;
+        }
+        write!(w, "]}}")
+    }
+
+    /// Finds every occurrence of `needle` on the page and returns one box per line it covers.
+    ///
+    /// Matching ignores case, whitespace and punctuation so a snippet taken from the
+    /// extracted text still matches when it was broken across lines or hyphenated.
+    /// Boxes cover whole words.
+    pub fn find_text(&self, needle: &str) -> Vec<BBox> {
+        let needle: Vec<char> = needle.chars().flat_map(normalize_for_search).collect();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        // the normalized page text together with the (block, line, word) each char came from
+        let mut haystack = Vec::new();
+        let mut origin = Vec::new();
+        for (b, block) in self.blocks.iter().enumerate() {
+            for (l, line) in block.lines.iter().enumerate() {
+                for (w, word) in line.words.iter().enumerate() {
+                    for c in word.text.chars().flat_map(normalize_for_search) {
+                        haystack.push(c);
+                        origin.push((b, l, w));
+                    }
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        let mut start = 0;
+        while start + needle.len() <= haystack.len() {
+            if haystack[start..start + needle.len()] != needle[..] {
+                start += 1;
+                continue;
+            }
+            let mut current: Option<((usize, usize), BBox)> = None;
+            let mut last_word = None;
+            for &(b, l, w) in &origin[start..start + needle.len()] {
+                if last_word == Some((b, l, w)) {
+                    continue;
+                }
+                last_word = Some((b, l, w));
+                let bbox = self.blocks[b].lines[l].words[w].bbox;
+                match current {
+                    Some((line, ref mut line_box)) if line == (b, l) => line_box.union(&bbox),
+                    _ => {
+                        result.extend(current.map(|(_, line_box)| line_box));
+                        current = Some(((b, l), bbox));
+                    }
+                }
+            }
+            result.extend(current.map(|(_, line_box)| line_box));
+            start += needle.len();
+        }
+        result
+    }
+}
+
+fn normalize_for_search(c: char) -> Vec<char> {
+    let expanded = match c {
+        '\u{fb00}' => "ff",
+        '\u{fb01}' => "fi",
+        '\u{fb02}' => "fl",
+        '\u{fb03}' => "ffi",
+        '\u{fb04}' => "ffl",
+        '\u{fb05}' | '\u{fb06}' => "st",
+        c if c.is_alphanumeric() => return c.to_lowercase().collect(),
+        _ => return Vec::new(),
+    };
+    expanded.chars().collect()
+}
+
+fn json_number(v: f64) -> String {
+    // JSON has no representation for NaN or infinities
+    if v.is_finite() { format!("{}", (v * 100.).round() / 100.) } else { "0".to_owned() }
+}
+
+fn json_bbox(b: &BBox) -> String {
+    format!("[{},{},{},{}]", json_number(b.x0), json_number(b.y0), json_number(b.x1), json_number(b.y1))
+}
+
+fn json_escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => result += "\\\"",
+            '\\' => result += "\\\\",
+            '\n' => result += "\\n",
+            '\r' => result += "\\r",
+            '\t' => result += "\\t",
+            c if (c as u32) < 0x20 => result += &format!("\\u{:04x}", c as u32),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => result += "&amp;",
+            '<' => result += "&lt;",
+            '>' => result += "&gt;",
+            '"' => result += "&quot;",
+            '\'' => result += "&apos;",
+            // control characters aren't allowed in XML
+            c if (c as u32) < 0x20 => result.push('\u{fffd}'),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Groups characters into words, lines and blocks using the same spacing
+/// heuristics as `PlainTextOutput` and collects them into `pages`
+pub struct LayoutOutput {
+    pub pages: Vec<LayoutPage>,
+    flip_ctm: Transform,
+    font: String,
+    in_word: bool,
+    last_end: f64,
+    last_y: f64,
+}
+
+impl LayoutOutput {
+    pub fn new() -> LayoutOutput {
+        LayoutOutput {
+            pages: Vec::new(),
+            flip_ctm: Transform2D::identity(),
+            font: String::new(),
+            in_word: false,
+            last_end: 0.,
+            last_y: 0.,
+        }
+    }
+}
+
+impl Default for LayoutOutput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OutputDev for LayoutOutput {
+    fn begin_page(&mut self, page_num: u32, page: &PageGeometry, _: Option<ArtBox>) -> Result<(), OutputError> {
+        self.flip_ctm = page.device_transform();
+        self.in_word = false;
+        self.pages.push(LayoutPage { page_num, width: page.width(), height: page.height(), blocks: Vec::new() });
+        Ok(())
+    }
+    fn end_page(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+    fn output_character(&mut self, trm: &Transform, width: f64, _spacing: f64, font_size: f64, char: &str) -> Result<(), OutputError> {
+        let position = trm.post_transform(&self.flip_ctm);
+        let transformed_font_size = transformed_font_size(trm, font_size);
+        let (x, y) = (position.m31, position.m32);
+        let end = x + width * transformed_font_size;
+        if char.trim().is_empty() {
+            self.in_word = false;
+            self.last_end = end;
+            return Ok(());
+        }
+
+        // The glyph box in text space runs from the descender to the ascender;
+        // map all four corners so that rotated text gets a sensible box too
+        let corners = [(0., -0.2), (width, -0.2), (0., 0.8), (width, 0.8)]
+            .map(|(cx, cy)| position.transform_point(point2(cx * font_size, cy * font_size)));
+        let mut bbox = BBox { x0: corners[0].x, y0: corners[0].y, x1: corners[0].x, y1: corners[0].y };
+        for c in &corners[1..] {
+            bbox.union(&BBox { x0: c.x, y0: c.y, x1: c.x, y1: c.y });
+        }
+
+        let page = match self.pages.last_mut() {
+            Some(page) => page,
+            None => return Ok(()),
+        };
+        let dy = y - self.last_y;
+        // a large vertical gap or moving back up the page (e.g. a new column) starts a new block
+        let new_block = page.blocks.is_empty() || dy.abs() > transformed_font_size * 1.5 || dy < -transformed_font_size * 0.5;
+        let new_line = new_block || dy.abs() > transformed_font_size * 0.5;
+        let new_word = new_line || !self.in_word
+            || x > self.last_end + transformed_font_size * 0.1
+            || x < self.last_end - transformed_font_size;
+
+        if new_block {
+            page.blocks.push(LayoutBlock { bbox, lines: Vec::new() });
+        }
+        let block = page.blocks.last_mut().unwrap();
+        block.bbox.union(&bbox);
+        if new_line {
+            block.lines.push(LayoutLine { bbox, words: Vec::new() });
+        }
+        let line = block.lines.last_mut().unwrap();
+        line.bbox.union(&bbox);
+        if new_word {
+            line.words.push(LayoutWord { text: String::new(), bbox, font: self.font.clone(), font_size: transformed_font_size });
+        }
+        let word = line.words.last_mut().unwrap();
+        word.bbox.union(&bbox);
+        word.text += char;
+
+        self.in_word = true;
+        self.last_y = y;
+        self.last_end = end;
+        Ok(())
+    }
+    fn begin_word(&mut self) -> Result<(), OutputError> {Ok(())}
+    fn end_word(&mut self) -> Result<(), OutputError> {Ok(())}
+    fn end_line(&mut self) -> Result<(), OutputError> {Ok(())}
+    fn set_font(&mut self, font_name: &str) -> Result<(), OutputError> {
+        if self.font != font_name {
+            self.font = font_name.to_owned();
+        }
+        Ok(())
+    }
+}
+
+/// Writes `{"pages": [...]}` where each page is formatted by `LayoutPage::to_json`.
+/// The closing brackets are written by `end_document` so use `output_doc` rather than `output_doc_page`.
+pub struct JSONOutput<'a> {
+    file: &'a mut dyn std::io::Write,
+    layout: LayoutOutput,
+    pages_written: usize,
+}
+
+impl<'a> JSONOutput<'a> {
+    pub fn new(file: &mut dyn std::io::Write) -> JSONOutput<'_> {
+        JSONOutput { file, layout: LayoutOutput::new(), pages_written: 0 }
+    }
+}
+
+impl<'a> OutputDev for JSONOutput<'a> {
+    fn begin_page(&mut self, page_num: u32, page: &PageGeometry, art_box: Option<ArtBox>) -> Result<(), OutputError> {
+        self.layout.begin_page(page_num, page, art_box)
+    }
+    fn end_page(&mut self) -> Result<(), OutputError> {
+        if let Some(page) = self.layout.pages.pop() {
+            let mut s = String::new();
+            s += if self.pages_written == 0 { "{\"pages\":[" } else { "," };
+            page.write_json(&mut s)?;
+            write!(self.file, "{}", s)?;
+            self.pages_written += 1;
+        }
+        Ok(())
+    }
+    fn output_character(&mut self, trm: &Transform, width: f64, spacing: f64, font_size: f64, char: &str) -> Result<(), OutputError> {
+        self.layout.output_character(trm, width, spacing, font_size, char)
+    }
+    fn begin_word(&mut self) -> Result<(), OutputError> {Ok(())}
+    fn end_word(&mut self) -> Result<(), OutputError> {Ok(())}
+    fn end_line(&mut self) -> Result<(), OutputError> {Ok(())}
+    fn set_font(&mut self, font_name: &str) -> Result<(), OutputError> {
+        self.layout.set_font(font_name)
+    }
+    fn end_document(&mut self) -> Result<(), OutputError> {
+        if self.pages_written == 0 {
+            write!(self.file, "{{\"pages\":[")?;
+        }
+        writeln!(self.file, "]}}")?;
+        Ok(())
+    }
+}
+
+/// Writes hOCR (https://kba.github.io/hocr-spec/1.2/) with one `ocr_page` per page.
+/// Like `JSONOutput` the document is only closed by `end_document`.
+pub struct HOCROutput<'a> {
+    file: &'a mut dyn std::io::Write,
+    layout: LayoutOutput,
+    header_written: bool,
+}
+
+impl<'a> HOCROutput<'a> {
+    pub fn new(file: &mut dyn std::io::Write) -> HOCROutput<'_> {
+        HOCROutput { file, layout: LayoutOutput::new(), header_written: false }
+    }
+    fn write_header(&mut self) -> Result<(), OutputError> {
+        if !self.header_written {
+            writeln!(self.file, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+            writeln!(self.file, "<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">")?;
+            writeln!(self.file, "<html xmlns=\"http://www.w3.org/1999/xhtml\">\n<head>\n<title></title>")?;
+            writeln!(self.file, "<meta http-equiv=\"Content-Type\" content=\"text/html;charset=utf-8\" />")?;
+            writeln!(self.file, "<meta name='ocr-system' content='pdf-extract' />")?;
+            writeln!(self.file, "<meta name='ocr-capabilities' content='ocr_page ocr_carea ocr_par ocr_line ocrx_word ocrp_font' />")?;
+            writeln!(self.file, "</head>\n<body>")?;
+            self.header_written = true;
+        }
+        Ok(())
+    }
+}
+
+fn hocr_bbox(b: &BBox) -> String {
+    format!("bbox {} {} {} {}", b.x0.floor() as i64, b.y0.floor() as i64, b.x1.ceil() as i64, b.y1.ceil() as i64)
+}
+
+impl<'a> OutputDev for HOCROutput<'a> {
+    fn begin_page(&mut self, page_num: u32, page: &PageGeometry, art_box: Option<ArtBox>) -> Result<(), OutputError> {
+        self.write_header()?;
+        self.layout.begin_page(page_num, page, art_box)
+    }
+    fn end_page(&mut self) -> Result<(), OutputError> {
+        let page = match self.layout.pages.pop() {
+            Some(page) => page,
+            None => return Ok(()),
+        };
+        let n = page.page_num;
+        let page_box = BBox { x0: 0., y0: 0., x1: page.width, y1: page.height };
+        writeln!(self.file, "<div class='ocr_page' id='page_{}' title='{}; ppageno {}'>", n, hocr_bbox(&page_box), n - 1)?;
+        let (mut line_id, mut word_id) = (0, 0);
+        for (i, block) in page.blocks.iter().enumerate() {
+            writeln!(self.file, "<div class='ocr_carea' id='block_{}_{}' title='{}'>", n, i + 1, hocr_bbox(&block.bbox))?;
+            writeln!(self.file, "<p class='ocr_par' id='par_{}_{}' title='{}'>", n, i + 1, hocr_bbox(&block.bbox))?;
+            for line in &block.lines {
+                line_id += 1;
+                write!(self.file, "<span class='ocr_line' id='line_{}_{}' title='{}'>", n, line_id, hocr_bbox(&line.bbox))?;
+                for (j, word) in line.words.iter().enumerate() {
+                    word_id += 1;
+                    if j > 0 { write!(self.file, " ")?; }
+                    write!(self.file, "<span class='ocrx_word' id='word_{}_{}' title='{}; x_font {}; x_fsize {}'>{}</span>",
+                           n, word_id, hocr_bbox(&word.bbox), xml_escape(&word.font), json_number(word.font_size), xml_escape(&word.text))?;
+                }
+                writeln!(self.file, "</span>")?;
+            }
+            writeln!(self.file, "</p>\n</div>")?;
+        }
+        writeln!(self.file, "</div>")?;
+        Ok(())
+    }
+    fn output_character(&mut self, trm: &Transform, width: f64, spacing: f64, font_size: f64, char: &str) -> Result<(), OutputError> {
+        self.layout.output_character(trm, width, spacing, font_size, char)
+    }
+    fn begin_word(&mut self) -> Result<(), OutputError> {Ok(())}
+    fn end_word(&mut self) -> Result<(), OutputError> {Ok(())}
+    fn end_line(&mut self) -> Result<(), OutputError> {Ok(())}
+    fn set_font(&mut self, font_name: &str) -> Result<(), OutputError> {
+        self.layout.set_font(font_name)
+    }
+    fn end_document(&mut self) -> Result<(), OutputError> {
+        self.write_header()?;
+        writeln!(self.file, "</body>\n</html>")?;
+        Ok(())
+    }
+}
+
 pub fn print_metadata(doc: &Document) {
     dlog!("Version: {}", doc.version);
     if let Some(ref info) = get_info(&doc) {
@@ -2242,12 +2859,16 @@ pub fn extract_text_from_mem_encrypted<PW: AsRef<[u8]>>(
 
 
 fn extract_text_by_page(doc: &Document, page_num: u32) -> Result<String, OutputError> {
+    extract_text_and_report_by_page(doc, page_num).map(|(s, _)| s)
+}
+
+fn extract_text_and_report_by_page(doc: &Document, page_num: u32) -> Result<(String, PageReport), OutputError> {
     let mut s = String::new();
-    {
+    let report = {
         let mut output = PlainTextOutput::new(&mut s);
-        output_doc_page(doc, &mut output, page_num)?;
-    }
-    Ok(s)
+        output_doc_page_with_report(doc, &mut output, page_num)?
+    };
+    Ok((s, report))
 }
 
 /// Extract the text from a pdf at `path` and return a `Vec<String>` with the results separately by page
@@ -2281,17 +2902,24 @@ pub fn extract_text_by_pages_encrypted<P: std::convert::AsRef<std::path::Path>,
 }
 
 pub fn extract_text_from_mem_by_pages(buffer: &[u8]) -> Result<Vec<String>, OutputError> {
+    extract_text_from_mem_by_pages_with_report(buffer).map(|(pages, _)| pages)
+}
+
+/// Like `extract_text_from_mem_by_pages` but also returns a `DocumentReport`
+pub fn extract_text_from_mem_by_pages_with_report(buffer: &[u8]) -> Result<(Vec<String>, DocumentReport), OutputError> {
     let mut v = Vec::new();
+    let mut report = DocumentReport::default();
     {
         let mut doc = Document::load_mem(buffer)?;
         maybe_decrypt(&mut doc)?;
         let mut page_num = 1;
-        while let Ok(content) = extract_text_by_page(&doc, page_num) {
+        while let Ok((content, page_report)) = extract_text_and_report_by_page(&doc, page_num) {
             v.push(content);
+            report.pages.push(page_report);
             page_num += 1;
         }
     }
-    Ok(v)
+    Ok((v, report))
 }
 
 pub fn extract_text_from_mem_by_pages_encrypted<PW: AsRef<[u8]>>(buffer: &[u8], password: PW) -> Result<Vec<String>, OutputError> {
@@ -2308,6 +2936,15 @@ pub fn extract_text_from_mem_by_pages_encrypted<PW: AsRef<[u8]>>(buffer: &[u8],
     Ok(v)
 }
 
+/// Extract the words of page `page_num` of a pdf grouped into blocks and lines with their positions
+pub fn extract_layout_from_mem_by_page(buffer: &[u8], page_num: u32) -> Result<LayoutPage, OutputError> {
+    let mut doc = Document::load_mem(buffer)?;
+    maybe_decrypt(&mut doc)?;
+    let mut output = LayoutOutput::new();
+    output_doc_page(&doc, &mut output, page_num)?;
+    Ok(output.pages.pop().expect("begin_page adds a page"))
+}
+
 
 fn get_inherited<'a, T: FromObj<'a>>(doc: &'a Document, dict: &'a Dictionary, key: &[u8]) -> Option<T> {
     let o: Option<T> = get(doc, dict, key);
@@ -2330,23 +2967,125 @@ pub fn output_doc_encrypted<PW: AsRef<[u8]>>(
     output_doc(doc, output)
 }
 
+/// How many of the characters shown on a page could not be decoded to text
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DecodeStats {
+    pub total_chars: usize,
+    pub undecodable_chars: usize,
+}
+
+impl DecodeStats {
+    /// Fraction of characters that came out as junk, 0 when nothing was shown
+    pub fn undecodable_fraction(&self) -> f64 {
+        if self.total_chars == 0 {
+            0.
+        } else {
+            self.undecodable_chars as f64 / self.total_chars as f64
+        }
+    }
+}
+
+/// What was painted on a page, used to tell text pages from pages that need OCR
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PaintStats {
+    /// Number of `Tj` and `TJ` operators, whether or not they produced any characters
+    pub text_operators: usize,
+    pub filled_paths: usize,
+    /// Path segments (moves, lines, curves, rectangles) across all filled paths
+    pub filled_segments: usize,
+    pub stroked_segments: usize,
+}
+
+/// Filled segments needed before a page without text is considered outlined text
+/// rather than a few decorative shapes. A single outlined glyph is around 10-40 segments.
+const OUTLINED_TEXT_MIN_SEGMENTS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageKind {
+    /// The page draws text with text operators
+    Text,
+    /// No text operators but mostly filled vector paths, typically labels converted
+    /// to outlines or a diagram. Rasterize and OCR these to get their text.
+    OutlinedText,
+    /// Neither text nor a significant amount of vector graphics
+    Empty,
+}
+
+#[derive(Debug, Clone)]
+pub struct PageReport {
+    pub page_num: u32,
+    pub decode: DecodeStats,
+    pub paint: PaintStats,
+}
+
+impl PageReport {
+    pub fn kind(&self) -> PageKind {
+        let paint = &self.paint;
+        if paint.text_operators > 0 {
+            PageKind::Text
+        } else if paint.filled_segments >= OUTLINED_TEXT_MIN_SEGMENTS && paint.filled_segments > paint.stroked_segments {
+            PageKind::OutlinedText
+        } else {
+            PageKind::Empty
+        }
+    }
+}
+
+/// Per page diagnostics gathered while extracting a document
+#[derive(Debug, Clone, Default)]
+pub struct DocumentReport {
+    pub pages: Vec<PageReport>,
+}
+
+impl DocumentReport {
+    /// The decoding statistics summed over all pages
+    pub fn decode(&self) -> DecodeStats {
+        self.pages.iter().fold(DecodeStats::default(), |acc, page| DecodeStats {
+            total_chars: acc.total_chars + page.decode.total_chars,
+            undecodable_chars: acc.undecodable_chars + page.decode.undecodable_chars,
+        })
+    }
+
+    pub fn undecodable_fraction(&self) -> f64 {
+        self.decode().undecodable_fraction()
+    }
+
+    /// Pages whose text is drawn as vector outlines and has to be recovered with OCR
+    pub fn outlined_text_pages(&self) -> Vec<u32> {
+        self.pages.iter().filter(|p| p.kind() == PageKind::OutlinedText).map(|p| p.page_num).collect()
+    }
+}
+
 /// Parse a given document and output it to `output`
 pub fn output_doc(doc: &Document, output: &mut dyn OutputDev) -> Result<(), OutputError> {
+    output_doc_with_report(doc, output)?;
+    Ok(())
+}
+
+/// Like `output_doc` but also returns a `DocumentReport` for the document
+pub fn output_doc_with_report(doc: &Document, output: &mut dyn OutputDev) -> Result<DocumentReport, OutputError> {
     if doc.is_encrypted() {
         eprintln!("Encrypted documents must be decrypted with a password using {{extract_text|extract_text_from_mem|output_doc}}_encrypted");
     }
     let empty_resources = Dictionary::new();
     let pages = doc.get_pages();
     let mut p = Processor::new();
+    let mut report = DocumentReport::default();
     for dict in pages {
         let page_num = dict.0;
         let object_id = dict.1;
-        output_doc_inner(page_num, object_id, doc, &mut p, output, &empty_resources)?;
+        report.pages.push(output_doc_inner(page_num, object_id, doc, &mut p, output, &empty_resources)?);
     }
-    Ok(())
+    output.end_document()?;
+    Ok(report)
 }
 
 pub fn output_doc_page(doc: &Document, output: &mut dyn OutputDev, page_num: u32) -> Result<(), OutputError> {
+    output_doc_page_with_report(doc, output, page_num)?;
+    Ok(())
+}
+
+pub fn output_doc_page_with_report(doc: &Document, output: &mut dyn OutputDev, page_num: u32) -> Result<PageReport, OutputError> {
     if doc.is_encrypted() {
         eprintln!("Encrypted documents must be decrypted with a password using {{extract_text|extract_text_from_mem|output_doc}}_encrypted");
     }
@@ -2354,11 +3093,10 @@ pub fn output_doc_page(doc: &Document, output: &mut dyn OutputDev, page_num: u32
     let pages = doc.get_pages();
     let object_id = pages.get(&page_num).ok_or(lopdf::Error::PageNumberNotFound(page_num))?;
     let mut p = Processor::new();
-    output_doc_inner(page_num, *object_id, doc, &mut p, output, &empty_resources)?;
-    Ok(())
+    output_doc_inner(page_num, *object_id, doc, &mut p, output, &empty_resources)
 }
 
-fn output_doc_inner<'a>(page_num: u32, object_id: ObjectId, doc: &'a Document, p: & mut Processor<'a>, output: &mut dyn OutputDev, empty_resources: &'a Dictionary) -> Result<(), OutputError> {
+fn output_doc_inner<'a>(page_num: u32, object_id: ObjectId, doc: &'a Document, p: & mut Processor<'a>, output: &mut dyn OutputDev, empty_resources: &'a Dictionary) -> Result<PageReport, OutputError> {
     let page_dict = doc.get_object(object_id).unwrap().as_dict().unwrap();
     dlog!("page {} {:?}", page_num, page_dict);
     // XXX: Some pdfs lack a Resources directory
@@ -2366,17 +3104,31 @@ fn output_doc_inner<'a>(page_num: u32, object_id: ObjectId, doc: &'a Document, p
     dlog!("resources {:?}", resources);
     // pdfium searches up the page tree for MediaBoxes as needed
     let media_box: Vec<f64> = get_inherited(doc, page_dict, b"MediaBox").expect("MediaBox");
-    let media_box = MediaBox { llx: media_box[0], lly: media_box[1], urx: media_box[2], ury: media_box[3] };
+    let media_box = MediaBox::from_array(&media_box);
+    // CropBox and Rotate are inheritable as well. The CropBox defaults to the MediaBox
+    let crop_box = get_inherited::<Vec<f64>>(doc, page_dict, b"CropBox")
+        .filter(|x| x.len() == 4)
+        .map(|x| MediaBox::from_array(&x));
+    let rotate = get_inherited::<i64>(doc, page_dict, b"Rotate").unwrap_or(0);
+    let page = PageGeometry::new(media_box, crop_box, rotate);
     let art_box = get::<Option<Vec<f64>>>(&doc, page_dict, b"ArtBox")
         .map(|x| (x[0], x[1], x[2], x[3]));
-    output.begin_page(page_num, &media_box, art_box)?;
-    p.process_stream(&doc, doc.get_page_content(object_id).unwrap(), resources, &media_box, output, page_num)?;
+    output.begin_page(page_num, &page, art_box)?;
+    p.stats = DecodeStats::default();
+    p.paint = PaintStats::default();
+    p.process_stream(&doc, doc.get_page_content(object_id).unwrap(), resources, &page, output, page_num)?;
     output.end_page()?;
-    Ok(())
+    Ok(PageReport { page_num, decode: p.stats, paint: p.paint })
 }
 
 // Add this new function
 pub fn extract_text_from_mem_by_pages_multithreaded(buffer: &[u8]) -> Result<Vec<String>, OutputError> {
+    extract_text_from_mem_by_pages_multithreaded_with_report(buffer).map(|(pages, _)| pages)
+}
+
+/// Like `extract_text_from_mem_by_pages_multithreaded` but also returns a `DocumentReport`
+/// covering the pages that were extracted
+pub fn extract_text_from_mem_by_pages_multithreaded_with_report(buffer: &[u8]) -> Result<(Vec<String>, DocumentReport), OutputError> {
     let mut doc = Document::load_mem(buffer)?;
     maybe_decrypt(&mut doc)?;
     
@@ -2387,8 +3139,8 @@ pub fn extract_text_from_mem_by_pages_multithreaded(buffer: &[u8]) -> Result<Vec
     // Process pages in parallel
     let results: Vec<_> = page_numbers.into_par_iter()
         .filter_map(|page_num| {
-            match extract_text_by_page(&doc, *page_num) {
-                Ok(content) => Some((*page_num, content)),
+            match extract_text_and_report_by_page(&doc, *page_num) {
+                Ok((content, report)) => Some((*page_num, content, report)),
                 Err(e) => {
                     eprintln!("Error processing page {}: {:?}", page_num, e);
                     None
@@ -2399,8 +3151,14 @@ pub fn extract_text_from_mem_by_pages_multithreaded(buffer: &[u8]) -> Result<Vec
     
     // Sort by page number to maintain order
     let mut sorted_results = results;
-    sorted_results.sort_by_key(|(num, _)| *num);
+    sorted_results.sort_by_key(|(num, _, _)| *num);
     
     // Extract just the content in order
-    Ok(sorted_results.into_iter().map(|(_, content)| content).collect())
+    let mut report = DocumentReport::default();
+    let mut pages = Vec::with_capacity(sorted_results.len());
+    for (_, content, page_report) in sorted_results {
+        pages.push(content);
+        report.pages.push(page_report);
+    }
+    Ok((pages, report))
 }
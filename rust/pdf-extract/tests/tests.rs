@@ -1,3 +1,4 @@
+use lopdf::dictionary;
 use pdf_extract::extract_text;
 
 // Shorthand for creating ExpectedText
@@ -80,3 +81,168 @@ impl ExpectedText<'_> {
         );
     }
 }
+
+/// Builds an in-memory document using Helvetica as `/F1`, one page per `(content, extra page entries)`
+fn make_pdf(pages: Vec<(&str, lopdf::Dictionary)>) -> Vec<u8> {
+    make_pdf_with_font(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Helvetica",
+    }, pages)
+}
+
+/// Like `make_pdf` but with `font` as `/F1`
+fn make_pdf_with_font(font: lopdf::Dictionary, pages: Vec<(&str, lopdf::Dictionary)>) -> Vec<u8> {
+    use lopdf::{Document, Object, Stream};
+    let mut doc = Document::with_version("1.5");
+    let pages_id = doc.new_object_id();
+    let font_id = doc.add_object(font);
+    let resources_id = doc.add_object(dictionary! {
+        "Font" => dictionary! { "F1" => font_id },
+    });
+    let mut kids = Vec::new();
+    for (content, extra) in pages {
+        let content_id = doc.add_object(Stream::new(dictionary! {}, content.as_bytes().to_vec()));
+        let mut page = dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        };
+        for (k, v) in extra {
+            page.set(k, v);
+        }
+        kids.push(Object::Reference(doc.add_object(page)));
+    }
+    let count = kids.len() as i64;
+    doc.objects.insert(pages_id, Object::Dictionary(dictionary! {
+        "Type" => "Pages",
+        "Kids" => kids,
+        "Count" => count,
+        "Resources" => resources_id,
+        "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+    }));
+    let catalog_id = doc.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    doc.trailer.set("Root", catalog_id);
+    let mut bytes = Vec::new();
+    doc.save_to(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn rotated_page_text_order() {
+    // A landscape page drawn on portrait paper: the content is rotated by the CTM and the page
+    // carries /Rotate 90 so that viewers show it upright.
+    let content = "q 0 1 -1 0 595 0 cm BT /F1 12 Tf 72 500 Td (First line) Tj 0 -14 Td (Second line) Tj ET Q";
+    let pdf = make_pdf(vec![(content, dictionary! { "Rotate" => 90 })]);
+    let out = pdf_extract::extract_text_from_mem(&pdf).unwrap();
+    assert!(out.contains("First line\nSecond line"), "{:?}", out);
+}
+
+#[test]
+fn page_geometry_crop_and_rotate() {
+    use pdf_extract::{MediaBox, PageGeometry};
+    let media_box = MediaBox { llx: 0., lly: 0., urx: 200., ury: 100. };
+    let crop_box = MediaBox { llx: 10., lly: 20., urx: 110., ury: 70. };
+
+    let page = PageGeometry::new(media_box, Some(crop_box), 0);
+    let top_left = page.device_transform().transform_point(euclid::point2(10., 70.));
+    assert_eq!((page.width(), page.height()), (100., 50.));
+    assert_eq!((top_left.x, top_left.y), (0., 0.));
+
+    let page = PageGeometry::new(media_box, None, -90);
+    assert_eq!(page.rotate, 270);
+    assert_eq!((page.width(), page.height()), (100., 200.));
+}
+
+#[test]
+fn symbolic_font_glyph_names() {
+    use lopdf::Object;
+    let font = dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Custom",
+        "FirstChar" => 1,
+        "LastChar" => 3,
+        "Widths" => vec![500.into(), 500.into(), 500.into()],
+        "FontDescriptor" => dictionary! { "Type" => "FontDescriptor", "FontName" => "Custom", "Flags" => 4 },
+        "Encoding" => dictionary! {
+            "Type" => "Encoding",
+            "Differences" => vec![1.into(), Object::Name(b"uni0048".to_vec()), Object::Name(b"i.sc".to_vec()), Object::Name(b"g17".to_vec())],
+        },
+    };
+    let pdf = make_pdf_with_font(font, vec![("BT /F1 12 Tf 72 700 Td <0102030301> Tj ET", dictionary! {})]);
+    let (pages, report) = pdf_extract::extract_text_from_mem_by_pages_with_report(&pdf).unwrap();
+    // The two unknown glyphs are flagged as a single run
+    assert_eq!(pages[0].trim_start(), "Hi\u{fffd}H");
+    assert_eq!(report.decode().total_chars, 5);
+    assert_eq!(report.decode().undecodable_chars, 2);
+    assert_eq!(report.undecodable_fraction(), 0.4);
+}
+
+#[test]
+fn layout_words_lines_and_blocks() {
+    let content = "BT /F1 10 Tf 72 742 Td (Hello world) Tj 0 -12 Td (next line) Tj 0 -48 Td (Another block) Tj ET";
+    let pdf = make_pdf(vec![(content, dictionary! {})]);
+    let page = pdf_extract::extract_layout_from_mem_by_page(&pdf, 1).unwrap();
+    assert_eq!((page.width, page.height), (595., 842.));
+    assert_eq!(page.blocks.len(), 2);
+    assert_eq!(page.blocks[0].lines.len(), 2);
+    let words: Vec<&str> = page.words().map(|w| w.text.as_str()).collect();
+    assert_eq!(words, ["Hello", "world", "next", "line", "Another", "block"]);
+
+    let hello = &page.blocks[0].lines[0].words[0];
+    assert_eq!(hello.font, "Helvetica");
+    assert_eq!(hello.font_size, 10.);
+    // baseline at 842 - 742 = 100 in device space
+    assert_eq!(hello.bbox.x0, 72.);
+    assert!(hello.bbox.y0 < 100. && hello.bbox.y1 > 100.);
+    assert!(hello.bbox.x1 < page.blocks[0].lines[0].words[1].bbox.x0);
+
+    let json = page.to_json();
+    assert!(json.starts_with("{\"page\":1,\"width\":595,\"height\":842,\"blocks\":[{\"bbox\":["));
+    assert!(json.contains("{\"text\":\"Hello\",\"bbox\":[72,"));
+
+    let mut hocr = Vec::new();
+    pdf_extract::output_doc(&lopdf::Document::load_mem(&pdf).unwrap(), &mut pdf_extract::HOCROutput::new(&mut hocr)).unwrap();
+    let hocr = String::from_utf8(hocr).unwrap();
+    assert!(hocr.contains("<div class='ocr_page' id='page_1' title='bbox 0 0 595 842; ppageno 0'>"));
+    assert!(hocr.contains("x_font Helvetica; x_fsize 10'>Hello</span>"));
+    assert!(hocr.ends_with("</body>\n</html>\n"));
+}
+
+#[test]
+fn find_text_across_hyphenated_lines() {
+    let content = "BT /F1 10 Tf 72 742 Td (A search engine for off-) Tj 0 -12 Td (line documents and more) Tj ET";
+    let pdf = make_pdf(vec![(content, dictionary! {})]);
+    let page = pdf_extract::extract_layout_from_mem_by_page(&pdf, 1).unwrap();
+    let line_boxes: Vec<_> = page.blocks[0].lines.iter().map(|l| l.bbox).collect();
+
+    let hits = page.find_text("Engine for  offline\nDocuments");
+    assert_eq!(hits.len(), 2);
+    // first box starts at "engine", second ends at "documents"
+    assert!(hits[0].x0 > line_boxes[0].x0 && hits[0].x1 == line_boxes[0].x1);
+    assert!(hits[1].x0 == line_boxes[1].x0 && hits[1].x1 < line_boxes[1].x1);
+    assert_eq!(page.find_text("not on the page"), []);
+    assert_eq!(page.find_text("  "), []);
+}
+
+#[test]
+fn outlined_text_pages_are_classified() {
+    use pdf_extract::PageKind;
+    // 30 filled "glyphs" of 10 segments each and no text operators
+    let glyph = "0 0 m 5 0 l 6 3 6 7 5 10 c 0 10 l 0 0 l 1 1 m 4 1 l 4 9 l 1 9 l h f*\n";
+    let outlined = format!("q 1 0 0 1 72 700 cm {} Q", glyph.repeat(30));
+    let pdf = make_pdf(vec![
+        ("BT /F1 12 Tf 72 700 Td (Plain text) Tj ET", dictionary! {}),
+        (outlined.as_str(), dictionary! {}),
+        ("0 0 595 842 re f", dictionary! {}),
+    ]);
+    let (_, report) = pdf_extract::extract_text_from_mem_by_pages_with_report(&pdf).unwrap();
+    let kinds: Vec<_> = report.pages.iter().map(|p| p.kind()).collect();
+    assert_eq!(kinds, [PageKind::Text, PageKind::OutlinedText, PageKind::Empty]);
+    assert_eq!(report.pages[1].paint.filled_paths, 30);
+    assert_eq!(report.outlined_text_pages(), [2]);
+}
@@ -0,0 +1,47 @@
+/*
+    Throughput of `encode_many` on 10k chunks.
+
+    The model directory defaults to models/BASE2M and can be set with RUSTPOTION_BENCH_MODEL.
+    Chunks are cut from RUSTPOTION_BENCH_CORPUS (any text file) when set, otherwise from a built-in paragraph.
+
+    RUSTPOTION_BENCH_MODEL=models/RETRIEVAL32M cargo bench --bench encode_many
+*/
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rustpotion::RustPotion;
+use std::path::PathBuf;
+
+const CHUNKS: usize = 10_000;
+const WORDS_PER_CHUNK: usize = 60;
+
+const TEXT: &str = "Apply firm, direct pressure to the wound with a clean cloth or bandage. \
+    If blood soaks through, add more layers and keep pressing; do not remove the first dressing. \
+    A tourniquet should only be used for life-threatening bleeding from an arm or leg, placed \
+    5-7 cm above the wound and tightened until the bleeding stops. Note the time it was applied. \
+    Keep the casualty warm, lying down, and reassure them while waiting for help. Check breathing \
+    and circulation every few minutes, and be ready to start CPR if they stop breathing normally.";
+
+fn chunks() -> Vec<String> {
+    let text = match std::env::var("RUSTPOTION_BENCH_CORPUS") {
+        Ok(path) => std::fs::read_to_string(path).unwrap(),
+        Err(_) => TEXT.to_string(),
+    };
+    let words: Vec<&str> = text.split_whitespace().collect();
+    (0..CHUNKS)
+        .map(|i| (0..WORDS_PER_CHUNK).map(|j| words[(i * 7 + j) % words.len()]).collect::<Vec<_>>().join(" "))
+        .collect()
+}
+
+fn encode_many(c: &mut Criterion) {
+    let model_dir = std::env::var("RUSTPOTION_BENCH_MODEL").map(PathBuf::from).unwrap_or_else(|_| PathBuf::from("models/BASE2M"));
+    let model = RustPotion::from_dir(&model_dir).unwrap();
+    let chunks = chunks();
+
+    let mut group = c.benchmark_group("encode_many");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(CHUNKS as u64));
+    group.bench_function("10k chunks", |b| b.iter(|| model.encode_many(chunks.clone())));
+    group.finish();
+}
+
+criterion_group!(benches, encode_many);
+criterion_main!(benches);
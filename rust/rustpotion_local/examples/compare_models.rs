@@ -0,0 +1,56 @@
+/*
+    Compares the retrieval results of two models on a corpus, e.g. an f32 model and its quantized copy.
+    Every line of the corpus file is a document, and the first 500 are also used as queries.
+
+    cargo run --release --example compare_models -- models/RETRIEVAL32M models/RETRIEVAL32M-int8 corpus.txt
+*/
+use rustpotion::RustPotion;
+use std::path::Path;
+
+const K: usize = 10;
+const QUERIES: usize = 500;
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn top_k(query: &[f32], documents: &[Vec<f32>]) -> Vec<usize> {
+    let mut scored: Vec<(usize, f32)> = documents.iter().enumerate().map(|(i, d)| (i, dot(query, d))).collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.into_iter().take(K).map(|(i, _)| i).collect()
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 4 {
+        eprintln!("usage: compare_models <reference model dir> <candidate model dir> <corpus file>");
+        std::process::exit(1);
+    }
+    let reference = RustPotion::from_dir(Path::new(&args[1])).unwrap();
+    let candidate = RustPotion::from_dir(Path::new(&args[2])).unwrap();
+    let corpus: Vec<String> = std::fs::read_to_string(&args[3]).unwrap()
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.to_string())
+        .collect();
+
+    let reference_embeddings = reference.encode_many(corpus.clone());
+    let candidate_embeddings = candidate.encode_many(corpus.clone());
+
+    // How close each embedding stays to its reference
+    let cosine: f32 = reference_embeddings.iter().zip(&candidate_embeddings).map(|(r, c)| dot(r, c)).sum::<f32>()
+        / corpus.len() as f32;
+
+    // Share of the reference top-k results the candidate also returns
+    let queries = corpus.len().min(QUERIES);
+    let mut overlap = 0;
+    for q in 0..queries {
+        let expected = top_k(&reference_embeddings[q], &reference_embeddings);
+        let actual = top_k(&candidate_embeddings[q], &candidate_embeddings);
+        overlap += actual.iter().filter(|i| expected.contains(i)).count();
+    }
+
+    println!("documents: {}, queries: {}", corpus.len(), queries);
+    println!("mean cosine to reference: {:.6}", cosine);
+    println!("recall@{} against reference: {:.4}", K, overlap as f32 / (queries * K) as f32);
+}
@@ -0,0 +1,37 @@
+/*
+    Prints how long a model takes to load and how much memory it holds on to.
+
+    cargo run --release --example load_stats -- path/to/model_dir
+*/
+use rustpotion::RustPotion;
+use std::path::Path;
+use std::time::Instant;
+
+// Resident and peak resident set size in kB, only available on Linux/Android
+fn memory_kb() -> Option<(u64, u64)> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let field = |name: &str| {
+        status.lines()
+            .find(|line| line.starts_with(name))
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|kb| kb.parse().ok())
+    };
+    Some((field("VmRSS:")?, field("VmHWM:")?))
+}
+
+fn main() {
+    let model_dir = std::env::args().nth(1).expect("usage: load_stats <model dir>");
+
+    let start = Instant::now();
+    let model = RustPotion::from_dir(Path::new(&model_dir)).unwrap();
+    let load_time = start.elapsed();
+
+    let start = Instant::now();
+    model.encode("The quick brown fox jumps over the lazy dog");
+    let first_encode = start.elapsed();
+
+    println!("load: {:?}, first encode: {:?}", load_time, first_encode);
+    if let Some((rss, peak)) = memory_kb() {
+        println!("rss: {} kB, peak rss: {} kB", rss, peak);
+    }
+}
@@ -0,0 +1,25 @@
+/*
+    Writes manifest.json for a model directory, so `RustPotion::from_dir` verifies the files on every load.
+    Run it once on the model you ship and bundle the manifest with it.
+
+    cargo run --release --example manifest -- models/RETRIEVAL32M potion-retrieval-32M
+*/
+use rustpotion::ModelManifest;
+use std::path::Path;
+use std::time::Instant;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 3 {
+        eprintln!("usage: manifest <model dir> <model name>");
+        std::process::exit(1);
+    }
+    let model_dir = Path::new(&args[1]);
+    let manifest = ModelManifest::create(model_dir, &args[2]).unwrap();
+    manifest.save(&model_dir.join("manifest.json")).unwrap();
+    println!("{}", serde_json::to_string_pretty(&manifest).unwrap());
+
+    let start = Instant::now();
+    manifest.verify_files(model_dir).unwrap();
+    println!("verifying the files took {:?}", start.elapsed());
+}
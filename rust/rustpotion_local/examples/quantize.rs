@@ -0,0 +1,33 @@
+/*
+    Writes a quantized copy of a model2vec model directory.
+
+    cargo run --release --example quantize -- models/RETRIEVAL32M models/RETRIEVAL32M-int8 int8
+*/
+use rustpotion::{quantize_embeddings, Quantization};
+use std::path::Path;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 4 {
+        eprintln!("usage: quantize <model dir> <output dir> <f16|bf16|int8>");
+        std::process::exit(1);
+    }
+    let (src, dst) = (Path::new(&args[1]), Path::new(&args[2]));
+    let quantization = match args[3].as_str() {
+        "f16" => Quantization::F16,
+        "bf16" => Quantization::BF16,
+        "int8" => Quantization::Int8,
+        other => panic!("unknown quantization {}", other),
+    };
+
+    std::fs::create_dir_all(dst).unwrap();
+    quantize_embeddings(&src.join("model.safetensors"), &dst.join("model.safetensors"), quantization).unwrap();
+    for file in ["tokenizer.json", "config.json"] {
+        if src.join(file).exists() {
+            std::fs::copy(src.join(file), dst.join(file)).unwrap();
+        }
+    }
+
+    let size = |dir: &Path| std::fs::metadata(dir.join("model.safetensors")).unwrap().len() as f64 / 1e6;
+    println!("{:.1} MB -> {:.1} MB", size(src), size(dst));
+}
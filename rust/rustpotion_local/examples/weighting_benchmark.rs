@@ -0,0 +1,73 @@
+/*
+    Measures how token weighting changes retrieval on a corpus with one document per line.
+    Every line with at least 16 words is split in half: the first half is used as the query and
+    the second half as the document it should find among all the other second halves.
+    IDF and SIF weights are computed from the documents, and the model's own weights are
+    included when the model file has a "weights" tensor.
+
+    cargo run --release --example weighting_benchmark -- models/RETRIEVAL32M corpus.txt
+*/
+use rustpotion::{RustPotion, TokenWeighting};
+use std::path::Path;
+
+const MIN_WORDS: usize = 16;
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+// 1-based rank of document `expected` for `query`
+fn rank(query: &[f32], documents: &[Vec<f32>], expected: usize) -> usize {
+    let score = dot(query, &documents[expected]);
+    1 + documents.iter().enumerate().filter(|(i, d)| *i != expected && dot(query, d) > score).count()
+}
+
+fn evaluate(name: &str, model: &RustPotion, queries: &[String], documents: &[String]) {
+    let query_embeddings = model.encode_many(queries.to_vec());
+    let document_embeddings = model.encode_many(documents.to_vec());
+    let ranks: Vec<usize> = query_embeddings.iter().enumerate()
+        .map(|(i, query)| rank(query, &document_embeddings, i))
+        .collect();
+    let recall = |k: usize| ranks.iter().filter(|&&r| r <= k).count() as f32 / ranks.len() as f32;
+    let mrr = ranks.iter().map(|&r| 1.0 / r as f32).sum::<f32>() / ranks.len() as f32;
+    println!("| {} | {:.4} | {:.4} | {:.4} |", name, recall(1), recall(10), mrr);
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 3 {
+        eprintln!("usage: weighting_benchmark <model dir> <corpus file>");
+        std::process::exit(1);
+    }
+    let model = RustPotion::from_dir(Path::new(&args[1])).unwrap();
+    let (queries, documents): (Vec<String>, Vec<String>) = std::fs::read_to_string(&args[2]).unwrap()
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .filter(|words| words.len() >= MIN_WORDS)
+        .map(|words| {
+            let (query, document) = words.split_at(words.len() / 2);
+            (query.join(" "), document.join(" "))
+        })
+        .unzip();
+
+    let idf = model.compute_token_weights(documents.iter().map(|d| d.as_str()), TokenWeighting::Idf).unwrap();
+    let sif = model.compute_token_weights(documents.iter().map(|d| d.as_str()), TokenWeighting::Sif { a: 1e-3 }).unwrap();
+    let model_weights = model.token_weights().map(|weights| weights.to_vec());
+
+    println!("pairs: {}", queries.len());
+    println!("| Weighting | Recall@1 | Recall@10 | MRR |");
+    println!("| --- | --- | --- | --- |");
+    let model = model.with_token_weights(None).unwrap();
+    evaluate("none", &model, &queries, &documents);
+    let model = if let Some(weights) = model_weights {
+        let model = model.with_token_weights(Some(weights)).unwrap();
+        evaluate("model", &model, &queries, &documents);
+        model
+    } else {
+        model
+    };
+    let model = model.with_token_weights(Some(idf)).unwrap();
+    evaluate("idf", &model, &queries, &documents);
+    let model = model.with_token_weights(Some(sif)).unwrap();
+    evaluate("sif", &model, &queries, &documents);
+}
@@ -1,126 +1,446 @@
-use safetensors::SafeTensors;
-use serde::Deserialize;
-use std::collections::HashMap;
+use safetensors::{Dtype, SafeTensors};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use regex::Regex;
+use tokenizers::{Model, ModelWrapper, NormalizedString, Normalizer, NormalizerWrapper, OffsetReferential, OffsetType, PreTokenizedString, PreTokenizer, PreTokenizerWrapper, Tokenizer};
 
+#[cfg(feature = "download")]
 use reqwest::blocking::get;
+#[cfg(feature = "download")]
 use std::fs::File;
+#[cfg(feature = "download")]
 use std::io::Write;
+#[cfg(feature = "download")]
+use std::path::PathBuf;
 use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{OnceLock, RwLock};
 
 use rayon::prelude::*;
 use memmap2::Mmap;
 use std::io::BufReader;
 
-#[derive(Deserialize)]
-struct Tokenizer {
-    model: ModelVocab,
+/*
+    Everything that can go wrong while loading a model or encoding text
+*/
+#[derive(Debug)]
+pub enum PotionError {
+    Io(std::io::Error),
+    Safetensors(safetensors::SafeTensorError),
+    MissingTensor(String),
+    UnsupportedDtype(String),
+    TokenizerParse(tokenizers::Error),
+    Config(String),
+    UnknownToken(String),
+    ManifestMismatch(String),
 }
 
-#[derive(Deserialize)]
-struct ModelVocab {
-    vocab: HashMap<String, i32>, 
-}
-
-/*
-    Converts a word into corresponding token(s) using the vocab. Matches largest from the right.
-    Not performant for long words (!!!)
-    Might be optimized with a cache for popular words
-*/ 
-fn word2tok(mut word: String, vocab: &HashMap<String, i32>) -> Result<Vec<i32>, ()> {
- 
-    let mut tokens = Vec::new();        // Resulting tokens
-    let mut i = word.len();                // Current split index
-
-    while !word.is_empty() {
-
-        /*
-            Some tokens are reserved if they appear within a word
-            Eg, the "able" token in "unable" vs "able" is different.
-
-            These unique tokens are "continuing-subwords", and are differentiated by starting with a ##.
-            In our case we can work backwards and just query our original target but with a ## when we 
-            are already working within a word.
-        */
-        let target = if tokens.len() > 0 {
-            format!("##{}", &word[..i])
-        } else {
-            word[..i].to_string()
-        };
+impl std::fmt::Display for PotionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PotionError::Io(e) => write!(f, "IO error: {}", e),
+            PotionError::Safetensors(e) => write!(f, "Could not read safetensors data: {}", e),
+            PotionError::MissingTensor(name) => write!(f, "Model has no \"{}\" tensor", name),
+            PotionError::UnsupportedDtype(dtype) => write!(f, "Embeddings of type {} are not supported", dtype),
+            PotionError::TokenizerParse(e) => write!(f, "Tokenizer error: {}", e),
+            PotionError::Config(msg) => write!(f, "Invalid model config: {}", msg),
+            PotionError::UnknownToken(word) => write!(f, "No tokens in the vocabulary match \"{}\"", word),
+            PotionError::ManifestMismatch(msg) => write!(f, "Model doesn't match its manifest: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for PotionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PotionError::Io(e) => Some(e),
+            PotionError::Safetensors(e) => Some(e),
+            PotionError::TokenizerParse(e) => Some(e.as_ref()),
+            PotionError::MissingTensor(_) | PotionError::UnsupportedDtype(_) | PotionError::Config(_) | PotionError::UnknownToken(_)
+            | PotionError::ManifestMismatch(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for PotionError {
+    fn from(e: std::io::Error) -> Self {
+        PotionError::Io(e)
+    }
+}
+
+impl From<safetensors::SafeTensorError> for PotionError {
+    fn from(e: safetensors::SafeTensorError) -> Self {
+        PotionError::Safetensors(e)
+    }
+}
+
+impl From<tokenizers::Error> for PotionError {
+    fn from(e: tokenizers::Error) -> Self {
+        PotionError::TokenizerParse(e)
+    }
+}
+
+/*
+    What to do with words (or subwords) that the vocabulary has no token for
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownTokenPolicy {
+    // Leave them out of the embedding, like model2vec does
+    #[default]
+    Skip,
+    // Average in the embedding of the model's unknown token
+    Unk,
+    // Fail the whole sentence with `PotionError::UnknownToken`
+    Error,
+}
+
+/*
+    Token counts for a single encode call
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EncodeStats {
+    // Tokens that went into the embedding
+    pub tokens: usize,
+    // Unknown tokens, either skipped or mapped to the unknown token depending on the policy
+    pub unknown_tokens: usize,
+    // Whether tokens past `LongText::Truncate`'s budget were left out
+    pub truncated: bool,
+}
+
+/*
+    How much a word adds to the cosine similarity of a match, see `RustPotion::explain_match`
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordContribution {
+    // First occurrence of the word, as written in the text
+    pub word: String,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchExplanation {
+    // Cosine similarity of the query and text embeddings
+    pub similarity: f32,
+    // Highest contributions first, only words that raise the similarity
+    pub query_words: Vec<WordContribution>,
+    pub text_words: Vec<WordContribution>,
+}
+
+/*
+    Words of a corpus that are a single vocabulary token, with their normalized (and projected) rows,
+    built by `RustPotion::corpus_words` and searched by `RustPotion::nearest_words`
+*/
+pub struct CorpusWords {
+    words: Vec<String>,
+    // words.len() rows of `dimensions` values
+    vectors: Vec<f32>,
+    dimensions: usize,
+}
+
+impl CorpusWords {
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
 
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/*
+    How `encode` handles texts with many tokens
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LongText {
+    // Average every token
+    #[default]
+    Full,
+    // Only the first `max_tokens` tokens. Tokenizing stops there, so this is the cheapest option for long texts
+    Truncate { max_tokens: usize },
+    // Embed windows of `window` tokens, `stride` tokens apart, and average the window embeddings,
+    // so every part of a long text counts the same however the text is split
+    SlidingWindow { window: usize, stride: usize },
+}
 
-        /*
-            Start actually querying the vocabulary
-         */
-        match vocab.get(&target) {
-            Some(&candidate) => {
-                
-                // Token found, push to results and reset split index
-                tokens.push(candidate);
-                word = word[i..].to_string();
-                i = word.len();
+impl LongText {
+    /*
+        Error for an empty budget or window, or a stride that would skip tokens
+    */
+    pub fn validate(self) -> Result<(), PotionError> {
+        match self {
+            LongText::Truncate { max_tokens: 0 } => Err(PotionError::Config("the token budget must be at least 1".to_string())),
+            LongText::SlidingWindow { window, stride } if window == 0 || stride == 0 || stride > window => {
+                Err(PotionError::Config(format!("invalid sliding window of {} tokens every {} tokens", window, stride)))
             },
-            
-            None => {
+            _ => Ok(()),
+        }
+    }
+}
 
-                // Unknown character in the word
-                if i == 0 {
-                    return Err(());
-                }
+/*
+    What a text is embedded as, for retrieval models that mark queries and documents differently,
+    see `ModelConfig::query_prefix` and `ModelConfig::query_projection`
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Query,
+    Document,
+}
+
+/*
+    Converts a word into corresponding token(s) with the model of the tokenizer (WordPiece, Unigram or BPE).
+    None if the model can't represent the word at all (no unknown token configured).
+*/
+fn word2tok(word: &str, model: &ModelWrapper) -> Option<Vec<u32>> {
+    model.tokenize(word)
+        .ok()
+        .map(|tokens| tokens.into_iter().map(|token| token.id).collect())
+}
+
+/*
+    Token ids of a cached word, and the pieces of it the model couldn't represent at all
+*/
+#[derive(Default)]
+struct WordTokens {
+    tokens: Vec<u32>,
+    missing: Vec<String>,
+}
+
+// Most chunks repeat the same few thousand words, so their token ids are kept after the first lookup
+const WORD_CACHE_CAPACITY: usize = 1 << 16;
+
+/*
+    word -> token ids, shared by all encoding threads. Stops growing at WORD_CACHE_CAPACITY words instead of evicting.
+*/
+#[derive(Default)]
+struct WordCache {
+    words: RwLock<HashMap<String, WordTokens>>,
+}
 
-                // Query missed, continuing shortening word
-                i -= 1;
+impl WordCache {
+    /*
+        Calls `f` with the cached tokens of `word`, running `tokenize` first if it isn't cached yet
+    */
+    fn with<R>(&self, word: &str, tokenize: impl FnOnce(&str) -> Result<WordTokens, PotionError>, f: impl FnOnce(&WordTokens) -> R) -> Result<R, PotionError> {
+        let full = {
+            let words = self.words.read().unwrap();
+            if let Some(tokens) = words.get(word) {
+                return Ok(f(tokens));
+            }
+            words.len() >= WORD_CACHE_CAPACITY
+        };
+        let tokens = tokenize(word)?;
+        let result = f(&tokens);
+        // Once full, misses don't take the write lock and block the readers for nothing
+        if !full {
+            let mut words = self.words.write().unwrap();
+            if words.len() < WORD_CACHE_CAPACITY {
+                words.insert(word.to_string(), tokens);
             }
         }
+        Ok(result)
+    }
+}
+
+// Word splitting for tokenizers without a pre-tokenizer, compiled once
+static WORD_REGEX: OnceLock<Regex> = OnceLock::new();
+
+/*
+    Runs the normalizer (lowercasing, accent handling, unicode normalization) and pre-tokenizer
+    configured in the model's tokenizer.json, and calls `f` with every resulting word.
+*/
+fn pre_tokenize(text: &str, tokenizer: &Tokenizer, mut f: impl FnMut(&str) -> Result<(), PotionError>) -> Result<(), PotionError> {
+    let mut normalized = NormalizedString::from(text);
+    if let Some(normalizer) = tokenizer.get_normalizer() {
+        normalizer.normalize(&mut normalized).map_err(PotionError::TokenizerParse)?;
     }
 
-    Ok(tokens)
+    match tokenizer.get_pre_tokenizer() {
+        Some(pre_tokenizer) => {
+            let mut pre_tokenized = PreTokenizedString::from(normalized);
+            pre_tokenizer.pre_tokenize(&mut pre_tokenized).map_err(PotionError::TokenizerParse)?;
+            for (word, _, _) in pre_tokenized.get_splits(OffsetReferential::Normalized, OffsetType::Byte) {
+                f(word)?;
+            }
+        },
+        None => {
+            /*
+                No pre-tokenizer configured: split into words & punctuation ourselves.
+                Regex is quite performant for this
+            */
+            let re = WORD_REGEX.get_or_init(|| Regex::new(r"[\w'-]+|[.,!?;]").unwrap());
+            for mat in re.find_iter(normalized.get()) {
+                f(mat.as_str())?;
+            }
+        }
+    }
+    Ok(())
 }
 
 /*
-    Split the sentence before attempting to find tokens, specifically to deal with punctuation & spaces.
+    Whether normalizing and pre-tokenizing a sentence gives the same words as doing it separately for
+    every whitespace separated part, so whole raw words can be cached. True for BERT-style tokenizers,
+    false for e.g. Metaspace or ByteLevel, which keep the spaces.
 */
-fn setencen2tok(sentence:&str, vocab:&HashMap<String, i32>) -> Result<Vec<i32>, ()> {
+fn splits_on_whitespace(tokenizer: &Tokenizer) -> bool {
+    fn char_local(normalizer: &NormalizerWrapper) -> bool {
+        match normalizer {
+            NormalizerWrapper::BertNormalizer(_) | NormalizerWrapper::Lowercase(_) | NormalizerWrapper::StripAccents(_)
+            | NormalizerWrapper::NFC(_) | NormalizerWrapper::NFD(_) | NormalizerWrapper::NFKC(_) | NormalizerWrapper::NFKD(_) => true,
+            NormalizerWrapper::Sequence(sequence) => sequence.as_ref().iter().all(char_local),
+            _ => false,
+        }
+    }
+    let normalizer = tokenizer.get_normalizer().is_none_or(char_local);
+    let pre_tokenizer = matches!(
+        tokenizer.get_pre_tokenizer(),
+        None | Some(PreTokenizerWrapper::BertPreTokenizer(_) | PreTokenizerWrapper::Whitespace(_) | PreTokenizerWrapper::WhitespaceSplit(_))
+    );
+    normalizer && pre_tokenizer
+}
 
-    /*
-        Splitting the sentence into words & punctuation
-        Regex is quite performant for this
-     */
+/*
+    Whitespace that separates raw words. Control characters are left out because BertNormalizer removes
+    them instead of turning them into spaces, which joins the text around them.
+*/
+fn is_word_separator(c: char) -> bool {
+    c.is_whitespace() && (!c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+}
+
+/*
+    Split the sentence before attempting to find tokens, specifically to deal with punctuation & spaces.
+    Words go through the model's own normalizer and pre-tokenizer (see `pre_tokenize`), so non-ASCII
+    text is handled the same way as in model2vec. Token ids are cached per raw word when
+    `whole_words` is set (see `splits_on_whitespace`), and per pre-tokenized word otherwise.
+*/
+fn setencen2tok(sentence:&str, tokenizer:&Tokenizer, word_cache: &WordCache, whole_words: bool, unk_id: Option<u32>, policy: UnknownTokenPolicy, max_tokens: usize) -> Result<(Vec<u32>, usize), PotionError> {
+    let model = tokenizer.get_model();
+    let tokenize_word = |word: &str, word_tokens: &mut WordTokens| {
+        match word2tok(word, model) {
+            Some(tokens) => word_tokens.tokens.extend(tokens),
+            None => word_tokens.missing.push(word.to_string()),
+        }
+    };
 
-    let re = Regex::new(r"[\w'-]+|[.,!?;]").unwrap();
-    let lower = sentence.to_lowercase();
-    let lower:String = lower.chars().filter(|&c| c.is_ascii()).collect();
-    let words:Vec<&str> = re.find_iter(&lower).map(|mat| mat.as_str()).collect();
-    
     /*
-        Accumulate found token ids
+        Accumulate found token ids, stopping once there are more than `max_tokens`.
+        add_word returns whether there is room for more.
      */
     let mut all_tokens = vec![];
-    for word in words {
-        all_tokens.extend(word2tok(word.to_string(), vocab)?)
+    let mut unknown = 0;
+    let mut add_word = |word: &str, word_tokens: &WordTokens| -> Result<bool, PotionError> {
+        if let (Some(missing), UnknownTokenPolicy::Error) = (word_tokens.missing.first(), policy) {
+            return Err(PotionError::UnknownToken(missing.clone()));
+        }
+        unknown += word_tokens.missing.len();
+        for &token in &word_tokens.tokens {
+            if Some(token) != unk_id {
+                all_tokens.push(token);
+                continue;
+            }
+            unknown += 1;
+            match policy {
+                UnknownTokenPolicy::Skip => {},
+                UnknownTokenPolicy::Unk => all_tokens.push(token),
+                UnknownTokenPolicy::Error => return Err(PotionError::UnknownToken(word.to_string())),
+            }
+        }
+        Ok(all_tokens.len() <= max_tokens)
+    };
+
+    if whole_words {
+        for raw_word in sentence.split(is_word_separator).filter(|word| !word.is_empty()) {
+            let tokenize = |raw_word: &str| {
+                let mut word_tokens = WordTokens::default();
+                pre_tokenize(raw_word, tokenizer, |word| {
+                    tokenize_word(word, &mut word_tokens);
+                    Ok(())
+                })?;
+                Ok(word_tokens)
+            };
+            if !word_cache.with(raw_word, tokenize, |word_tokens| add_word(raw_word, word_tokens))?? {
+                break;
+            }
+        }
+    } else {
+        let mut full = false;
+        pre_tokenize(sentence, tokenizer, |word| {
+            if full {
+                return Ok(());
+            }
+            let tokenize = |word: &str| {
+                let mut word_tokens = WordTokens::default();
+                tokenize_word(word, &mut word_tokens);
+                Ok(word_tokens)
+            };
+            full = !word_cache.with(word, tokenize, |word_tokens| add_word(word, word_tokens))??;
+            Ok(())
+        })?;
     }
 
-    return Ok(all_tokens)
+    Ok((all_tokens, unknown))
+}
+
+/*
+    Words of `text` as written (for display), their normalized form and their token ids,
+    skipping unknown tokens unless the policy is `UnknownTokenPolicy::Unk`
+*/
+fn words_with_tokens(text: &str, tokenizer: &Tokenizer, unk_id: Option<u32>, policy: UnknownTokenPolicy) -> Result<Vec<(String, String, Vec<u32>)>, PotionError> {
+    let mut normalized = NormalizedString::from(text);
+    if let Some(normalizer) = tokenizer.get_normalizer() {
+        normalizer.normalize(&mut normalized).map_err(PotionError::TokenizerParse)?;
+    }
+
+    let words: Vec<(String, String)> = match tokenizer.get_pre_tokenizer() {
+        Some(pre_tokenizer) => {
+            let mut pre_tokenized = PreTokenizedString::from(normalized);
+            pre_tokenizer.pre_tokenize(&mut pre_tokenized).map_err(PotionError::TokenizerParse)?;
+            pre_tokenized.get_splits(OffsetReferential::Original, OffsetType::Byte)
+                .into_iter()
+                .map(|(word, (start, end), _)| (text.get(start..end).unwrap_or(word).to_string(), word.to_string()))
+                .collect()
+        },
+        None => {
+            let re = WORD_REGEX.get_or_init(|| Regex::new(r"[\w'-]+|[.,!?;]").unwrap());
+            re.find_iter(normalized.get()).map(|mat| (mat.as_str().to_string(), mat.as_str().to_string())).collect()
+        },
+    };
 
+    let model = tokenizer.get_model();
+    Ok(words.into_iter()
+        .map(|(original, word)| {
+            let tokens = word2tok(&word, model).unwrap_or_default()
+                .into_iter()
+                .filter(|&token| Some(token) != unk_id || policy == UnknownTokenPolicy::Unk)
+                .collect();
+            (original, word, tokens)
+        })
+        .collect())
 }
 
 /*
-    Mainly used for loading the model weights as safetensor returns binary data
+    Id of the token the model falls back to for words it can't represent, if it has one
 */
-fn u8_to_f32_vec(v: &[u8]) -> Vec<f32> {
-    v.par_chunks_exact(4)
-        .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
-        .collect()
+fn unk_token_id(tokenizer: &Tokenizer) -> Option<u32> {
+    let unk_token = match tokenizer.get_model() {
+        ModelWrapper::WordPiece(model) => Some(model.unk_token.clone()),
+        ModelWrapper::WordLevel(model) => Some(model.unk_token.clone()),
+        ModelWrapper::BPE(model) => model.unk_token.clone(),
+        // Unigram keeps its unk id private, model2vec models use the sentencepiece default
+        ModelWrapper::Unigram(_) => Some("<unk>".to_string()),
+    };
+    unk_token.and_then(|token| tokenizer.token_to_id(&token))
 }
 
 /*
     Returns size of vector, used later to position embedding on unit circle. 
 */
-fn norm(v: &Vec<f32>) -> f32 {
+fn norm(v: &[f32]) -> f32 {
     let sum_of_squares: f32 = v.iter().map(|&x| x * x).sum();
     sum_of_squares.sqrt()
 }
 
+#[cfg(feature = "download")]
 fn download_file(url: &str, destination: &Path) -> Result<(), Box<dyn std::error::Error>> {
     let response = get(url)?;
     if !response.status().is_success() {
@@ -135,243 +455,1304 @@ fn download_file(url: &str, destination: &Path) -> Result<(), Box<dyn std::error
 }
 
 /*
-    Read disk path and load in model "weights"
+    Downloads model.safetensors, tokenizer.json and config.json of `model_kind` from the minishlab
+    Hugging Face repositories into `working_directory/<model_kind>`, for `RustPotion::try_new`.
+    Only available with the "download" feature, the app itself ships its models as assets.
 */
-fn load_embeddings(safetensors_dst: &Path) -> (Vec<f32>, usize) {
-    let file = std::fs::File::open(safetensors_dst).unwrap();
-    // Memory map the file - this is more efficient for large files
-    let mmap = unsafe { Mmap::map(&file).unwrap() };
-    let emb_tensors = SafeTensors::deserialize(&mmap).unwrap();
-    let embeddings = emb_tensors.tensor("embeddings").unwrap();
-    (u8_to_f32_vec(embeddings.data()), embeddings.shape()[1])
+#[cfg(feature = "download")]
+pub fn download_model(model_kind: PotionModel, working_directory: &Path) -> Result<PathBuf, PotionError> {
+    let repository = match model_kind {
+        PotionModel::RETRIEVAL32M => "potion-retrieval-32M",
+        PotionModel::BASE8M => "potion-base-8M",
+        PotionModel::BASE4M => "potion-base-4M",
+        PotionModel::BASE2M => "potion-base-2M",
+    };
+    let model_dir = working_directory.join(model_kind.to_string());
+    std::fs::create_dir_all(&model_dir)?;
+    for file in ["model.safetensors", "tokenizer.json", "config.json"] {
+        let url = format!("https://huggingface.co/minishlab/{}/resolve/main/{}", repository, file);
+        println!("Downloading {}", url);
+        download_file(&url, &model_dir.join(file))
+            .map_err(|e| PotionError::Io(std::io::Error::other(format!("{}: {}", url, e))))?;
+    }
+    Ok(model_dir)
 }
 
 /*
-    Read tokenizer.json - we ignore everything other than vocabulary data for the sake of simplicity
+    How the values of the embedding table are stored
 */
-fn load_vocab(tokenizer_dst: &Path) -> HashMap<String, i32> {
-    let file = std::fs::read_to_string(tokenizer_dst).expect("Unable to read file");
-    let tokenizer: Tokenizer = serde_json::from_str(&file).unwrap();
-    tokenizer.model.vocab
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TableType {
+    F32,
+    F16,
+    BF16,
+    // One byte per value, multiplied by the f32 "scales" entry of its row
+    I8 { scales_offset: usize },
 }
 
-/*
-    Primary struct for loading/inferencing the model
-*/
-pub struct RustPotion {
-    embeddings: Vec<f32>, // flattened 2d array: (vocab, dimensions)
-    dimensions: usize,
-    vocab: HashMap<String, i32>
+impl TableType {
+    fn value_size(&self) -> usize {
+        match self {
+            TableType::F32 => 4,
+            TableType::F16 | TableType::BF16 => 2,
+            TableType::I8 { .. } => 1,
+        }
+    }
 }
 
 /*
-    Enum for each provided model from minishlab
+    Bytes of a safetensors file: mapped from disk, or handed over in memory (e.g. a Flutter asset)
 */
-#[derive(Debug)]
-pub enum PotionModel {
-    RETRIEVAL32M,
-    BASE8M,
-    BASE4M,
-    BASE2M,
+enum TableData {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+    Static(&'static [u8]),
 }
 
-/* Additional function for organising destination paths on disk */
-impl std::fmt::Display for PotionModel {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+impl std::ops::Deref for TableData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            TableData::Mapped(mmap) => mmap,
+            TableData::Owned(bytes) => bytes,
+            TableData::Static(bytes) => bytes,
+        }
     }
 }
 
-impl RustPotion {
-    /*
-        Load model and vocabulary
-        
-        Example:
-        ```
-        use rustpotion::{RustPotion, PotionModel};
-        let model =  RustPotion::new(PotionModel::BASE2M);
-        model.encode("Hello, World!");
-        ```
-     */
-    pub fn new(model_kind: PotionModel, working_directory: &Path) -> Self {
-        println!("Starting model initialization...");
-        println!("Model kind: {:?}", model_kind);
-        println!("Base working directory: {}", working_directory.display());
-        
-        // Create the model destination directory if it doesn't exist
-        let working_directory = working_directory.join(model_kind.to_string());
-        println!("Full working directory path: {}", working_directory.display());
-        
-        if !working_directory.exists() {
-            println!("Creating model directory at: {}", working_directory.display());
-            std::fs::create_dir_all(&working_directory).unwrap();
-        }
+/*
+    The model "weights": a (vocab, dimensions) table that stays in the memory mapped
+    safetensors file. Rows are decoded as they are read, so loading costs no copy and
+    only the pages of tokens that are actually used become resident.
+    Tables can be f32, f16, bf16 or int8 with a per row scale (see `quantize_embeddings`).
+*/
+struct EmbeddingMatrix {
+    data: TableData,
+    offset: usize, // start of the tensor data in the file
+    rows: usize,
+    dimensions: usize,
+    table: TableType,
+}
 
-        // Define destination paths
-        let safetensors_path = working_directory.join("model.safetensors");
-        let tokenizer_path = working_directory.join("tokenizer.json");
+fn tensor_offset(tensor: &safetensors::tensor::TensorView, data: &[u8]) -> usize {
+    tensor.data().as_ptr() as usize - data.as_ptr() as usize
+}
 
-        println!("Checking paths:");
-        println!("  Model path: {}", safetensors_path.display());
-        println!("  Tokenizer path: {}", tokenizer_path.display());
+impl EmbeddingMatrix {
+    /*
+        Read disk path and locate the "embeddings" tensor (and "scales" for int8 tables)
+    */
+    fn open(safetensors_dst: &Path) -> Result<Self, PotionError> {
+        let file = std::fs::File::open(safetensors_dst)?;
+        // Memory map the file - this is more efficient for large files
+        let mmap = unsafe { Mmap::map(&file)? };
+        Self::from_data(TableData::Mapped(mmap))
+    }
 
-        // Check if files exist
-        println!("\nChecking files:");
-        println!("  Model exists: {}", safetensors_path.exists());
-        println!("  Tokenizer exists: {}", tokenizer_path.exists());
+    fn from_data(data: TableData) -> Result<Self, PotionError> {
+        let (offset, rows, dimensions, table) = {
+            let emb_tensors = SafeTensors::deserialize(&data)?;
+            let embeddings = emb_tensors.tensor("embeddings")
+                .map_err(|_| PotionError::MissingTensor("embeddings".to_string()))?;
+            let (rows, dimensions) = match embeddings.shape() {
+                &[rows, dimensions] => (rows, dimensions),
+                _ => return Err(PotionError::MissingTensor("embeddings".to_string())),
+            };
+            let table = match embeddings.dtype() {
+                Dtype::F32 => TableType::F32,
+                Dtype::F16 => TableType::F16,
+                Dtype::BF16 => TableType::BF16,
+                Dtype::I8 => {
+                    let scales = emb_tensors.tensor("scales")
+                        .ok()
+                        .filter(|scales| scales.dtype() == Dtype::F32 && scales.shape().iter().product::<usize>() == rows)
+                        .ok_or_else(|| PotionError::MissingTensor("scales".to_string()))?;
+                    TableType::I8 { scales_offset: tensor_offset(&scales, &data) }
+                },
+                dtype => return Err(PotionError::UnsupportedDtype(format!("{:?}", dtype))),
+            };
+            (tensor_offset(&embeddings, &data), rows, dimensions, table)
+        };
+        Ok(Self { data, offset, rows, dimensions, table })
+    }
 
-        // List directory contents
-        if working_directory.exists() {
-            println!("\nDirectory contents of {}:", working_directory.display());
-            if let Ok(entries) = std::fs::read_dir(&working_directory) {
-                for entry in entries {
-                    if let Ok(entry) = entry {
-                        println!("  - {}", entry.path().display());
+    /*
+        Adds row `id` times `weight` to `out`, false if the id is outside the table
+    */
+    fn add_row(&self, id: usize, weight: f32, out: &mut [f32]) -> bool {
+        if id >= self.rows {
+            return false;
+        }
+        let row_len = self.dimensions * self.table.value_size();
+        let start = self.offset + id * row_len;
+        // The data isn't necessarily aligned, so decode each value instead of casting
+        let row = &self.data[start..start + row_len];
+        match self.table {
+            TableType::F32 => {
+                // safetensors aligns tensor data, so on little endian targets the row can be read in place,
+                // which lets the compiler vectorize the loop
+                let (prefix, values, suffix) = unsafe { row.align_to::<f32>() };
+                if cfg!(target_endian = "little") && prefix.is_empty() && suffix.is_empty() {
+                    for (o, v) in out.iter_mut().zip(values) {
+                        *o += weight * v;
+                    }
+                } else {
+                    for (o, bytes) in out.iter_mut().zip(row.chunks_exact(4)) {
+                        *o += weight * f32::from_le_bytes(bytes.try_into().unwrap());
                     }
                 }
-            }
-        }
-
-        // Load the model files
-        println!("Loading model into memory...");
-        let file = match std::fs::File::open(&safetensors_path) {
-            Ok(f) => {
-                println!("Successfully opened safetensors file");
-                f
             },
-            Err(e) => {
-                println!("Failed to open safetensors file: {}", e);
-                panic!("Could not open safetensors file: {}", e);
-            }
-        };
-
-        let mmap = unsafe { 
-            match Mmap::map(&file) {
-                Ok(m) => {
-                    println!("Successfully memory mapped safetensors file");
-                    m
-                },
-                Err(e) => {
-                    println!("Failed to memory map safetensors file: {}", e);
-                    panic!("Could not memory map safetensors file: {}", e);
+            TableType::F16 => {
+                for (o, bytes) in out.iter_mut().zip(row.chunks_exact(2)) {
+                    *o += weight * half::f16::from_le_bytes(bytes.try_into().unwrap()).to_f32();
                 }
-            }
-        };
-
-        let emb_tensors = match SafeTensors::deserialize(&mmap) {
-            Ok(t) => {
-                println!("Successfully deserialized safetensors data");
-                t
-            },
-            Err(e) => {
-                println!("Failed to deserialize safetensors data: {}", e);
-                panic!("Could not deserialize safetensors data: {}", e);
-            }
-        };
-
-        let embeddings = match emb_tensors.tensor("embeddings") {
-            Ok(e) => {
-                println!("Successfully retrieved embeddings tensor");
-                e
             },
-            Err(e) => {
-                println!("Failed to get embeddings tensor: {}", e);
-                panic!("Could not get embeddings tensor: {}", e);
-            }
-        };
-
-        let (embeddings, dimensions) = (u8_to_f32_vec(embeddings.data()), embeddings.shape()[1]);
-        println!("Successfully loaded embeddings: {} dimensions", dimensions);
-
-        // Load vocab
-        println!("Loading tokenizer from: {}", tokenizer_path.display());
-        let vocab = {
-            let file_content = match std::fs::read_to_string(&tokenizer_path) {
-                Ok(content) => {
-                    println!("Successfully read tokenizer file");
-                    content
-                },
-                Err(e) => {
-                    println!("Failed to read tokenizer file: {}", e);
-                    panic!("Could not read tokenizer file: {}", e);
+            TableType::BF16 => {
+                for (o, bytes) in out.iter_mut().zip(row.chunks_exact(2)) {
+                    *o += weight * half::bf16::from_le_bytes(bytes.try_into().unwrap()).to_f32();
                 }
-            };
-
-            match serde_json::from_str::<Tokenizer>(&file_content) {
-                Ok(tokenizer) => {
-                    println!("Successfully parsed tokenizer JSON");
-                    tokenizer.model.vocab
-                },
-                Err(e) => {
-                    println!("Failed to parse tokenizer JSON: {}", e);
-                    panic!("Could not parse tokenizer JSON: {}", e);
+            },
+            TableType::I8 { scales_offset } => {
+                let scale_start = scales_offset + id * 4;
+                let scale = weight * f32::from_le_bytes(self.data[scale_start..scale_start + 4].try_into().unwrap());
+                for (o, &byte) in out.iter_mut().zip(row) {
+                    *o += byte as i8 as f32 * scale;
                 }
-            }
-        };
-
-        println!("Model loaded successfully:");
-        println!("  - Dimensions: {}", dimensions);
-        println!("  - Vocabulary size: {}", vocab.len());
-
-        Self {
-            embeddings,
-            dimensions,
-            vocab
+            },
         }
+        true
     }
 
-    /* 
-        Primary function for converting a single sentence into an embedding.
-    */
-    pub fn encode(&self, sentence: &str) -> Vec<f32> {
-
-        let tokens = setencen2tok(sentence, &self.vocab).unwrap();
-        let mut out_array = vec![0.0f32; self.dimensions];
+    fn row(&self, id: usize) -> Option<Vec<f32>> {
+        let mut row = vec![0.0f32; self.dimensions];
+        self.add_row(id, 1.0, &mut row).then_some(row)
+    }
 
-        if sentence.len() == 0 {
-            return out_array
+    /*
+        Per-token "weights" tensor stored beside the embeddings, if the model has one
+    */
+    fn weights(&self) -> Result<Option<Vec<f32>>, PotionError> {
+        let tensors = SafeTensors::deserialize(&self.data)?;
+        match tensors.tensor("weights") {
+            Ok(_) => read_weights(&tensors, self.rows).map(Some),
+            Err(_) => Ok(None),
         }
+    }
+}
 
-        for token in tokens.iter() {
-            let tmp_arr = self.embeddings[*token as usize * self.dimensions .. (*token as usize * self.dimensions) + self.dimensions].to_vec();
-            for i in 0..tmp_arr.len() {
-                out_array[i] += tmp_arr[i]
-            }
-        }
+/*
+    Storage formats `quantize_embeddings` can write
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantization {
+    F16,
+    BF16,
+    // int8 values with one f32 scale per row (max abs value / 127)
+    Int8,
+}
 
-        for i in 0..out_array.len() {
-            out_array[i] /= tokens.len() as f32;
+/*
+    Writes a copy of a model2vec safetensors file with the embedding table stored as `quantization`.
+    Any other tensors in the file are copied unchanged.
+*/
+pub fn quantize_embeddings(src: &Path, dst: &Path, quantization: Quantization) -> Result<(), PotionError> {
+    let matrix = EmbeddingMatrix::open(src)?;
+    let mut table = Vec::with_capacity(matrix.rows * matrix.dimensions * 2);
+    let mut scales = Vec::new();
+    for id in 0..matrix.rows {
+        let row = matrix.row(id).unwrap();
+        match quantization {
+            Quantization::F16 => table.extend(row.iter().flat_map(|&x| half::f16::from_f32(x).to_le_bytes())),
+            Quantization::BF16 => table.extend(row.iter().flat_map(|&x| half::bf16::from_f32(x).to_le_bytes())),
+            Quantization::Int8 => {
+                let max = row.iter().fold(0.0f32, |max, x| max.max(x.abs()));
+                let scale = if max > 0.0 { max / 127.0 } else { 1.0 };
+                table.extend(row.iter().map(|&x| (x / scale).round().clamp(-127.0, 127.0) as i8 as u8));
+                scales.extend(scale.to_le_bytes());
+            },
         }
-    
-        let n = norm(&out_array);
+    }
 
-        for i in 0..out_array.len() {
-            out_array[i] /=  n;
+    let dtype = match quantization {
+        Quantization::F16 => Dtype::F16,
+        Quantization::BF16 => Dtype::BF16,
+        Quantization::Int8 => Dtype::I8,
+    };
+    let source = SafeTensors::deserialize(&matrix.data)?;
+    let mut tensors = vec![
+        ("embeddings".to_string(), safetensors::tensor::TensorView::new(dtype, vec![matrix.rows, matrix.dimensions], &table)?),
+    ];
+    if quantization == Quantization::Int8 {
+        tensors.push(("scales".to_string(), safetensors::tensor::TensorView::new(Dtype::F32, vec![matrix.rows], &scales)?));
+    }
+    for (name, tensor) in source.tensors() {
+        if name != "embeddings" && name != "scales" {
+            tensors.push((name, tensor));
         }
-
-        out_array
     }
+    safetensors::serialize_to_file(tensors, &None, dst)?;
+    Ok(())
+}
 
-    /* 
-        Useful for converting multiple sentences into their respective embeddings.
-        Will use all available threads.
-    */
-    pub fn encode_many(&self, sentences: Vec<String>) -> Vec<Vec<f32>> {
-        sentences.par_iter() 
-        .map(|sentence| self.encode(sentence))
-        .collect()
-    }
+/*
+    How `RustPotion::compute_token_weights` weighs tokens by their frequency in a corpus
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenWeighting {
+    // ln((1 + documents) / (1 + documents containing the token)) + 1
+    Idf,
+    // a / (a + p(token)), where p is the token's share of all tokens. 1e-3 is the usual value for a
+    Sif { a: f32 },
+}
 
+fn read_weights(tensors: &SafeTensors, rows: usize) -> Result<Vec<f32>, PotionError> {
+    let weights = tensors.tensor("weights")
+        .ok()
+        .filter(|weights| weights.dtype() == Dtype::F32)
+        .ok_or_else(|| PotionError::MissingTensor("weights".to_string()))?;
+    if weights.shape() != [rows] {
+        return Err(PotionError::Config(format!("expected {} token weights, found shape {:?}", rows, weights.shape())));
+    }
+    Ok(weights.data().chunks_exact(4).map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap())).collect())
 }
 
+/*
+    Read a weights file written by `save_token_weights`
+*/
+pub fn load_token_weights(weights_dst: &Path) -> Result<Vec<f32>, PotionError> {
+    let file = std::fs::read(weights_dst)?;
+    let tensors = SafeTensors::deserialize(&file)?;
+    let rows = tensors.tensor("weights").map(|weights| weights.shape().iter().product()).unwrap_or(0);
+    read_weights(&tensors, rows)
+}
 
-#[cfg(test)]
-mod tests {
+/*
+    Write token weights as a safetensors file with a single f32 "weights" tensor
+*/
+pub fn save_token_weights(weights: &[f32], weights_dst: &Path) -> Result<(), PotionError> {
+    let data: Vec<u8> = weights.iter().flat_map(|w| w.to_le_bytes()).collect();
+    let view = safetensors::tensor::TensorView::new(Dtype::F32, vec![weights.len()], &data)?;
+    safetensors::serialize_to_file([("weights", view)], &None, weights_dst)?;
+    Ok(())
+}
+
+/*
+    Reduces pooled embeddings to fewer dimensions, see `RustPotion::with_truncation` and `RustPotion::with_pca`
+*/
+enum Projection {
+    // Keep the first n dimensions, for Matryoshka-style models
+    Truncate(usize),
+    // components is a row major (dimensions, model dimensions) matrix
+    Pca { components: Vec<f32>, mean: Option<Vec<f32>>, dimensions: usize },
+    // A linear layer, weight is a row major (dimensions, model dimensions) matrix
+    Linear { weight: Vec<f32>, bias: Option<Vec<f32>>, dimensions: usize },
+}
+
+/*
+    Feed `values` to `hasher` as little-endian bytes
+*/
+fn hash_f32s(hasher: &mut Sha256, values: &[f32]) {
+    let bytes: Vec<u8> = values.iter().flat_map(|x| x.to_le_bytes()).collect();
+    hasher.update(bytes);
+}
+
+impl Projection {
+    fn dimensions(&self) -> usize {
+        match self {
+            Projection::Truncate(dimensions) => *dimensions,
+            Projection::Pca { dimensions, .. } => *dimensions,
+            Projection::Linear { dimensions, .. } => *dimensions,
+        }
+    }
+
+    fn apply(&self, mut v: Vec<f32>) -> Vec<f32> {
+        match self {
+            Projection::Truncate(dimensions) => {
+                v.truncate(*dimensions);
+                v
+            },
+            Projection::Pca { components, mean, dimensions } => {
+                if let Some(mean) = mean {
+                    for (x, m) in v.iter_mut().zip(mean) {
+                        *x -= m;
+                    }
+                }
+                components.chunks_exact(v.len())
+                    .take(*dimensions)
+                    .map(|component| component.iter().zip(&v).map(|(c, x)| c * x).sum())
+                    .collect()
+            },
+            Projection::Linear { weight, bias, dimensions } => {
+                let mut out: Vec<f32> = weight.chunks_exact(v.len())
+                    .take(*dimensions)
+                    .map(|row| row.iter().zip(&v).map(|(w, x)| w * x).sum())
+                    .collect();
+                if let Some(bias) = bias {
+                    for (x, b) in out.iter_mut().zip(bias) {
+                        *x += b;
+                    }
+                }
+                out
+            },
+        }
+    }
+}
+
+/*
+    Values of the f32 tensor `name`, MissingTensor if there is none with a shape `shape_ok` accepts
+*/
+fn f32_tensor(tensors: &SafeTensors, name: &str, shape_ok: &dyn Fn(&[usize]) -> bool) -> Result<Vec<f32>, PotionError> {
+    let tensor = tensors.tensor(name)
+        .ok()
+        .filter(|tensor| tensor.dtype() == Dtype::F32 && shape_ok(tensor.shape()))
+        .ok_or_else(|| PotionError::MissingTensor(name.to_string()))?;
+    Ok(tensor.data().chunks_exact(4).map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap())).collect())
+}
+
+/*
+    Read a PCA projection: a "components" (n, model dimensions) f32 tensor and an optional "mean" (model dimensions) f32 tensor
+*/
+fn load_pca(pca_dst: &Path, model_dimensions: usize, dimensions: Option<usize>) -> Result<Projection, PotionError> {
+    let file = std::fs::read(pca_dst)?;
+    let tensors = SafeTensors::deserialize(&file)?;
+
+    let components = f32_tensor(&tensors, "components", &|shape| shape.len() == 2 && shape[1] == model_dimensions)?;
+    let mean = match tensors.tensor("mean") {
+        Ok(_) => Some(f32_tensor(&tensors, "mean", &|shape| shape == [model_dimensions])?),
+        Err(_) => None,
+    };
+    let available = components.len() / model_dimensions;
+    let dimensions = dimensions.unwrap_or(available);
+    if dimensions == 0 || dimensions > available {
+        return Err(PotionError::Config(format!("{} has {} components, {} requested", pca_dst.display(), available, dimensions)));
+    }
+    Ok(Projection::Pca { components, mean, dimensions })
+}
+
+/*
+    Read a query-side projection: a square "weight" (model dimensions, model dimensions) f32 tensor and an
+    optional "bias" (model dimensions) f32 tensor, mapping pooled queries into the space of the documents
+*/
+fn load_query_projection(projection_dst: &Path, model_dimensions: usize) -> Result<Projection, PotionError> {
+    let file = std::fs::read(projection_dst)?;
+    let tensors = SafeTensors::deserialize(&file)?;
+
+    let weight = f32_tensor(&tensors, "weight", &|shape| shape == [model_dimensions, model_dimensions])?;
+    let bias = match tensors.tensor("bias") {
+        Ok(_) => Some(f32_tensor(&tensors, "bias", &|shape| shape == [model_dimensions])?),
+        Err(_) => None,
+    };
+    Ok(Projection::Linear { weight, bias, dimensions: model_dimensions })
+}
+
+/*
+    Read tokenizer.json with its normalizer, pre-tokenizer and model
+*/
+fn load_tokenizer(tokenizer_dst: &Path) -> Result<Tokenizer, PotionError> {
+    let file = std::fs::read(tokenizer_dst)?;
+    Ok(Tokenizer::from_bytes(file)?)
+}
+
+/*
+    Settings from a model2vec config.json. Missing files or keys fall back to the defaults
+    model2vec uses, so plain tokenlearn/potion directories keep working.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelConfig {
+    // Scale embeddings to unit length after mean pooling
+    pub normalize: bool,
+    // Number of PCA components the embeddings were reduced to when the model was distilled, checked
+    // against the tensor. model2vec skips the PCA when it asks for more components than there are dimensions.
+    pub apply_pca: Option<usize>,
+    // Expected embedding width, checked against the tensor
+    pub dimension: Option<usize>,
+    // Put before texts embedded as `Role::Query` / `Role::Document`, e.g. "query: " and "passage: "
+    pub query_prefix: Option<String>,
+    pub document_prefix: Option<String>,
+    // File beside config.json with a projection applied to pooled queries only, see `RustPotion::with_query_projection`
+    pub query_projection: Option<String>,
+}
+
+impl Default for ModelConfig {
+    fn default() -> Self {
+        Self { normalize: true, apply_pca: None, dimension: None, query_prefix: None, document_prefix: None, query_projection: None }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawModelConfig {
+    normalize: Option<bool>,
+    // An int, null, or a float / "auto" that was only meaningful while distilling
+    apply_pca: Option<serde_json::Value>,
+    #[serde(alias = "dimension")]
+    hidden_dim: Option<usize>,
+    // Named like sentence-transformers prompts: "query", and "document" or "passage"
+    prompts: Option<HashMap<String, String>>,
+    query_projection: Option<String>,
+}
+
+fn load_config(config_dst: &Path) -> Result<ModelConfig, PotionError> {
+    let file = std::fs::read(config_dst)?;
+    parse_config(&file, &config_dst.display().to_string())
+}
+
+fn parse_config(config: &[u8], source: &str) -> Result<ModelConfig, PotionError> {
+    let raw: RawModelConfig = serde_json::from_slice(config)
+        .map_err(|e| PotionError::Config(format!("{}: {}", source, e)))?;
+    let mut prompts = raw.prompts.unwrap_or_default();
+    Ok(ModelConfig {
+        normalize: raw.normalize.unwrap_or(true),
+        apply_pca: raw.apply_pca.and_then(|v| v.as_u64()).map(|v| v as usize),
+        dimension: raw.hidden_dim,
+        query_prefix: prompts.remove("query"),
+        document_prefix: prompts.remove("document").or_else(|| prompts.remove("passage")),
+        query_projection: raw.query_projection,
+    })
+}
+
+// Files of a model directory that `ModelManifest::create` records, if present
+const MANIFEST_FILES: [&str; 3] = ["model.safetensors", "tokenizer.json", "config.json"];
+
+/*
+    Lowercase hex SHA-256 of a file, read in chunks so large tables aren't loaded at once
+*/
+fn sha256_file(path: &Path) -> Result<String, PotionError> {
+    let mut reader = BufReader::with_capacity(1 << 20, std::fs::File::open(path)?);
+    let mut hasher = Sha256::new();
+    loop {
+        let chunk = std::io::BufRead::fill_buf(&mut reader)?;
+        if chunk.is_empty() {
+            break;
+        }
+        hasher.update(chunk);
+        let read = chunk.len();
+        std::io::BufRead::consume(&mut reader, read);
+    }
+    Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/*
+    Identity of a model directory, stored beside it as manifest.json. `RustPotion::from_dir` checks the
+    files against it, so a truncated copy or a mismatched tokenizer fails at load time instead of
+    producing wrong vectors.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelManifest {
+    pub name: String,
+    // Width of the embedding table
+    pub dimensions: usize,
+    pub vocab_size: usize,
+    // File name in the model directory -> SHA-256 (hex)
+    pub files: BTreeMap<String, String>,
+}
+
+impl ModelManifest {
+    /*
+        Describe the model in `model_dir`, hashing its model.safetensors, tokenizer.json and config.json
+     */
+    pub fn create(model_dir: &Path, name: &str) -> Result<Self, PotionError> {
+        let config_path = model_dir.join("config.json");
+        let model = RustPotion::from_files(
+            &model_dir.join("model.safetensors"),
+            &model_dir.join("tokenizer.json"),
+            Some(config_path.as_path()).filter(|path| path.exists()),
+        )?;
+        let mut files = BTreeMap::new();
+        for file in MANIFEST_FILES {
+            let path = model_dir.join(file);
+            if path.exists() {
+                files.insert(file.to_string(), sha256_file(&path)?);
+            }
+        }
+        Ok(Self {
+            name: name.to_string(),
+            dimensions: model.dimensions,
+            vocab_size: model.tokenizer.get_vocab_size(false),
+            files,
+        })
+    }
+
+    pub fn load(manifest_dst: &Path) -> Result<Self, PotionError> {
+        let file = std::fs::read(manifest_dst)?;
+        serde_json::from_slice(&file)
+            .map_err(|e| PotionError::Config(format!("{}: {}", manifest_dst.display(), e)))
+    }
+
+    pub fn save(&self, manifest_dst: &Path) -> Result<(), PotionError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| PotionError::Config(e.to_string()))?;
+        std::fs::write(manifest_dst, json)?;
+        Ok(())
+    }
+
+    /*
+        Check the hash of every listed file in `model_dir`. Every load hashes them again: a size and
+        modification time check would pass a file corrupted in place, and the model directory may be read-only.
+     */
+    pub fn verify_files(&self, model_dir: &Path) -> Result<(), PotionError> {
+        for (file, expected) in &self.files {
+            let path = model_dir.join(file);
+            if !path.exists() {
+                return Err(PotionError::ManifestMismatch(format!("{} is missing", path.display())));
+            }
+            let actual = sha256_file(&path)?;
+            if &actual != expected {
+                return Err(PotionError::ManifestMismatch(format!("{} has SHA-256 {}, expected {}", path.display(), actual, expected)));
+            }
+        }
+        Ok(())
+    }
+
+    /*
+        Check the loaded model's shape against the manifest
+     */
+    fn verify_model(&self, model: &RustPotion) -> Result<(), PotionError> {
+        let vocab_size = model.tokenizer.get_vocab_size(false);
+        if model.dimensions != self.dimensions || vocab_size != self.vocab_size {
+            return Err(PotionError::ManifestMismatch(format!(
+                "{} expects {} dimensions and {} tokens, the model has {} and {}",
+                self.name, self.dimensions, self.vocab_size, model.dimensions, vocab_size,
+            )));
+        }
+        Ok(())
+    }
+}
+
+/*
+    Which model built a vector index, saved next to the index. `RustPotion::check_index` refuses to
+    mix vectors from another model, or from the same model reduced to a different size.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexManifest {
+    pub model: ModelManifest,
+    // Length of the stored vectors, after any truncation or PCA
+    pub dimensions: usize,
+}
+
+impl IndexManifest {
+    pub fn load(manifest_dst: &Path) -> Result<Self, PotionError> {
+        let file = std::fs::read(manifest_dst)?;
+        serde_json::from_slice(&file)
+            .map_err(|e| PotionError::Config(format!("{}: {}", manifest_dst.display(), e)))
+    }
+
+    pub fn save(&self, manifest_dst: &Path) -> Result<(), PotionError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| PotionError::Config(e.to_string()))?;
+        std::fs::write(manifest_dst, json)?;
+        Ok(())
+    }
+}
+
+/*
+    Primary struct for loading/inferencing the model
+*/
+pub struct RustPotion {
+    embeddings: EmbeddingMatrix,
+    dimensions: usize,
+    tokenizer: Tokenizer,
+    unk_id: Option<u32>,
+    unknown_tokens: UnknownTokenPolicy,
+    config: ModelConfig,
+    projection: Option<Projection>,
+    query_projection: Option<Projection>,
+    weights: Option<Vec<f32>>,
+    word_cache: WordCache,
+    whole_words: bool,
+    manifest: Option<ModelManifest>,
+    long_text: LongText,
+}
+
+/*
+    Enum for each provided model from minishlab
+*/
+#[derive(Debug)]
+pub enum PotionModel {
+    RETRIEVAL32M,
+    BASE8M,
+    BASE4M,
+    BASE2M,
+}
+
+/* Additional function for organising destination paths on disk */
+impl std::fmt::Display for PotionModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl RustPotion {
+    /*
+        Load model and vocabulary
+        
+        Example:
+        ```
+        use rustpotion::{RustPotion, PotionModel};
+        let model =  RustPotion::new(PotionModel::BASE2M);
+        model.encode("Hello, World!");
+        ```
+     */
+    pub fn new(model_kind: PotionModel, working_directory: &Path) -> Self {
+        Self::try_new(model_kind, working_directory).unwrap()
+    }
+
+    /*
+        Like `new`, but returns an error instead of panicking when the model files are missing or broken
+     */
+    pub fn try_new(model_kind: PotionModel, working_directory: &Path) -> Result<Self, PotionError> {
+        println!("Starting model initialization...");
+        println!("Model kind: {:?}", model_kind);
+
+        // Create the model destination directory if it doesn't exist
+        let working_directory = working_directory.join(model_kind.to_string());
+        println!("Full working directory path: {}", working_directory.display());
+
+        if !working_directory.exists() {
+            println!("Creating model directory at: {}", working_directory.display());
+            std::fs::create_dir_all(&working_directory)?;
+        }
+
+        Self::from_dir(&working_directory)
+    }
+
+    /*
+        Load any model2vec-format directory: model.safetensors, tokenizer.json and an optional config.json
+
+        Example:
+        ```
+        use rustpotion::RustPotion;
+        let model = RustPotion::from_dir(Path::new("models/potion-base-8M"))?;
+        ```
+     */
+    pub fn from_dir(model_dir: &Path) -> Result<Self, PotionError> {
+        // Verify the files before reading them when the directory has a manifest.json
+        let manifest_path = model_dir.join("manifest.json");
+        let manifest = match manifest_path.exists() {
+            true => {
+                println!("Verifying model against: {}", manifest_path.display());
+                let manifest = ModelManifest::load(&manifest_path)?;
+                manifest.verify_files(model_dir)?;
+                Some(manifest)
+            },
+            false => None,
+        };
+
+        let config_path = model_dir.join("config.json");
+        let mut model = Self::from_files(
+            &model_dir.join("model.safetensors"),
+            &model_dir.join("tokenizer.json"),
+            Some(config_path.as_path()).filter(|path| path.exists()),
+        )?;
+        if let Some(manifest) = &manifest {
+            manifest.verify_model(&model)?;
+        }
+        model.manifest = manifest;
+        Ok(model)
+    }
+
+    /*
+        Load a model from explicitly named files, e.g. assets that were renamed when bundling
+     */
+    pub fn from_files(safetensors_path: &Path, tokenizer_path: &Path, config_path: Option<&Path>) -> Result<Self, PotionError> {
+        let config = match config_path {
+            Some(config_path) => {
+                println!("Loading config from: {}", config_path.display());
+                load_config(config_path)?
+            },
+            None => ModelConfig::default(),
+        };
+
+        println!("Loading model from: {}", safetensors_path.display());
+        let embeddings = EmbeddingMatrix::open(safetensors_path)?;
+
+        println!("Loading tokenizer from: {}", tokenizer_path.display());
+        let tokenizer = load_tokenizer(tokenizer_path)?;
+        let query_projection = config_path.zip(config.query_projection.clone())
+            .map(|(config_path, file)| config_path.with_file_name(file));
+        let model = Self::from_parts(embeddings, tokenizer, config)?;
+        match query_projection {
+            Some(query_projection) => model.with_query_projection(&query_projection),
+            None => Ok(model),
+        }
+    }
+
+    /*
+        Load a model from bytes that are already in memory, e.g. Flutter assets handed over as buffers,
+        so nothing has to be copied to the file system first. The safetensors bytes are kept as they are.
+     */
+    pub fn from_bytes(safetensors: Vec<u8>, tokenizer: &[u8], config: Option<&[u8]>) -> Result<Self, PotionError> {
+        Self::from_data(TableData::Owned(safetensors), tokenizer, config)
+    }
+
+    /*
+        Like `from_bytes`, for a model compiled into the binary with `include_bytes!`.
+        As with `from_bytes`, a query projection named in the config is left to `with_query_projection`.
+     */
+    pub fn from_static(safetensors: &'static [u8], tokenizer: &[u8], config: Option<&[u8]>) -> Result<Self, PotionError> {
+        Self::from_data(TableData::Static(safetensors), tokenizer, config)
+    }
+
+    fn from_data(safetensors: TableData, tokenizer: &[u8], config: Option<&[u8]>) -> Result<Self, PotionError> {
+        let config = match config {
+            Some(config) => parse_config(config, "config.json")?,
+            None => ModelConfig::default(),
+        };
+        println!("Loading model from {} bytes", safetensors.len());
+        let embeddings = EmbeddingMatrix::from_data(safetensors)?;
+        let tokenizer = Tokenizer::from_bytes(tokenizer)?;
+        Self::from_parts(embeddings, tokenizer, config)
+    }
+
+    fn from_parts(embeddings: EmbeddingMatrix, tokenizer: Tokenizer, config: ModelConfig) -> Result<Self, PotionError> {
+        let dimensions = embeddings.dimensions;
+        let weights = embeddings.weights()?;
+        if let Some(expected) = config.dimension.filter(|&expected| expected != dimensions) {
+            return Err(PotionError::Config(format!("config.json expects {} dimensions but the embeddings have {}", expected, dimensions)));
+        }
+        // A table wider than apply_pca wasn't reduced as config.json says. A narrower one is fine: model2vec
+        // skips the PCA when it asks for more components than there are dimensions.
+        if let Some(components) = config.apply_pca.filter(|&components| dimensions > components) {
+            return Err(PotionError::Config(format!("config.json says the embeddings were reduced to {} PCA components but they have {} dimensions", components, dimensions)));
+        }
+
+        let unk_id = unk_token_id(&tokenizer);
+        let whole_words = splits_on_whitespace(&tokenizer);
+
+        println!("Model loaded successfully:");
+        println!("  - Dimensions: {}", dimensions);
+        println!("  - Vocabulary size: {}", tokenizer.get_vocab_size(false));
+        println!("  - Normalize: {}", config.normalize);
+        println!("  - Token weights: {}", weights.is_some());
+
+        Ok(Self {
+            embeddings,
+            dimensions,
+            tokenizer,
+            unk_id,
+            unknown_tokens: UnknownTokenPolicy::default(),
+            config,
+            projection: None,
+            query_projection: None,
+            weights,
+            word_cache: WordCache::default(),
+            whole_words,
+            manifest: None,
+            long_text: LongText::default(),
+        })
+    }
+
+    /*
+        Size of the embeddings returned by `encode`, after any truncation or PCA
+    */
+    pub fn dimensions(&self) -> usize {
+        self.projection.as_ref().map_or(self.dimensions, Projection::dimensions)
+    }
+
+    /*
+        Only keep the first `dimensions` values of every embedding (before normalizing).
+        Meant for Matryoshka-style models that put the most important information first.
+     */
+    pub fn with_truncation(mut self, dimensions: usize) -> Result<Self, PotionError> {
+        if dimensions == 0 || dimensions > self.dimensions {
+            return Err(PotionError::Config(format!("can't truncate {} dimensions to {}", self.dimensions, dimensions)));
+        }
+        self.projection = Some(Projection::Truncate(dimensions));
+        Ok(self)
+    }
+
+    /*
+        Project every embedding onto the PCA components stored in `pca_path` (before normalizing),
+        keeping the first `dimensions` components or all of them
+     */
+    pub fn with_pca(mut self, pca_path: &Path, dimensions: Option<usize>) -> Result<Self, PotionError> {
+        self.projection = Some(load_pca(pca_path, self.dimensions, dimensions)?);
+        Ok(self)
+    }
+
+    /*
+        Apply the projection in `projection_path` to pooled queries (`Role::Query`) before anything else,
+        for asymmetric models whose queries live in a different space than their documents.
+        `from_dir` and `from_files` load the one config.json names by themselves.
+     */
+    pub fn with_query_projection(mut self, projection_path: &Path) -> Result<Self, PotionError> {
+        println!("Loading query projection from: {}", projection_path.display());
+        self.query_projection = Some(load_query_projection(projection_path, self.dimensions)?);
+        Ok(self)
+    }
+
+    /*
+        Pool with a weighted instead of a plain mean, one weight per row of the embedding table.
+        Replaces the "weights" tensor of the model file, if any; `None` goes back to a plain mean.
+     */
+    pub fn with_token_weights(mut self, weights: Option<Vec<f32>>) -> Result<Self, PotionError> {
+        if let Some(weights) = &weights {
+            if weights.len() != self.embeddings.rows {
+                return Err(PotionError::Config(format!("expected {} token weights, got {}", self.embeddings.rows, weights.len())));
+            }
+        }
+        self.weights = weights;
+        Ok(self)
+    }
+
+    pub fn token_weights(&self) -> Option<&[f32]> {
+        self.weights.as_deref()
+    }
+
+    /*
+        Weigh every token of the embedding table by how often it occurs in `corpus`, e.g. the indexed
+        sections. Tokens that never occur get the highest weight. Save the result with `save_token_weights`.
+     */
+    pub fn compute_token_weights<'a>(&self, corpus: impl IntoIterator<Item = &'a str>, weighting: TokenWeighting) -> Result<Vec<f32>, PotionError> {
+        let rows = self.embeddings.rows;
+        let mut counts = vec![0usize; rows];
+        let mut document_counts = vec![0usize; rows];
+        let mut documents = 0usize;
+        let mut seen = Vec::new();
+        for text in corpus {
+            let (tokens, _) = setencen2tok(text, &self.tokenizer, &self.word_cache, self.whole_words, self.unk_id, UnknownTokenPolicy::Skip, usize::MAX)?;
+            seen.clear();
+            for token in tokens.into_iter().map(|token| token as usize).filter(|&token| token < rows) {
+                counts[token] += 1;
+                seen.push(token);
+            }
+            seen.sort_unstable();
+            seen.dedup();
+            for &token in &seen {
+                document_counts[token] += 1;
+            }
+            documents += 1;
+        }
+
+        Ok(match weighting {
+            TokenWeighting::Idf => document_counts.iter()
+                .map(|&df| ((1 + documents) as f32 / (1 + df) as f32).ln() + 1.0)
+                .collect(),
+            TokenWeighting::Sif { a } => {
+                let total = counts.iter().sum::<usize>().max(1) as f32;
+                counts.iter().map(|&count| a / (a + count as f32 / total)).collect()
+            },
+        })
+    }
+
+    /*
+        Hex SHA-256 of everything that decides the embeddings: the table (or, for a model checked against
+        its manifest.json, the hashes listed there), tokenizer, config, token weights and projections.
+        Without a manifest the whole table is read, so callers should compute it once per model.
+     */
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        match &self.manifest {
+            Some(manifest) => {
+                for (file, sha256) in &manifest.files {
+                    hasher.update(format!("{}={}\n", file, sha256));
+                }
+            },
+            None => hasher.update(&self.embeddings.data[..]),
+        }
+        hasher.update(self.tokenizer.to_string(false).unwrap_or_default());
+        hasher.update(format!("{:?}\0{:?}\0{}\0", self.config, self.unknown_tokens, self.dimensions()));
+        if let Some(weights) = &self.weights {
+            hash_f32s(&mut hasher, weights);
+        }
+        for (name, projection) in [("projection", &self.projection), ("query_projection", &self.query_projection)] {
+            let Some(projection) = projection else { continue };
+            hasher.update(name);
+            match projection {
+                Projection::Truncate(dimensions) => hasher.update(dimensions.to_le_bytes()),
+                Projection::Pca { components, mean, .. } => {
+                    hash_f32s(&mut hasher, components);
+                    hash_f32s(&mut hasher, mean.as_deref().unwrap_or_default());
+                },
+                Projection::Linear { weight, bias, .. } => {
+                    hash_f32s(&mut hasher, weight);
+                    hash_f32s(&mut hasher, bias.as_deref().unwrap_or_default());
+                },
+            }
+        }
+        hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /*
+        The verified manifest.json of the model directory, if it had one
+     */
+    pub fn manifest(&self) -> Option<&ModelManifest> {
+        self.manifest.as_ref()
+    }
+
+    /*
+        What to save beside an index built with this model, None without a manifest
+     */
+    pub fn index_manifest(&self) -> Option<IndexManifest> {
+        self.manifest.clone().map(|model| IndexManifest { model, dimensions: self.dimensions() })
+    }
+
+    /*
+        Error unless the vectors of the index described by `index` came from this model, with the same size
+     */
+    pub fn check_index(&self, index: &IndexManifest) -> Result<(), PotionError> {
+        match self.index_manifest() {
+            Some(current) if current == *index => Ok(()),
+            Some(current) if current.model == index.model => Err(PotionError::ManifestMismatch(format!(
+                "the index has {} dimensional vectors, the model returns {}", index.dimensions, current.dimensions,
+            ))),
+            Some(current) => Err(PotionError::ManifestMismatch(format!(
+                "the index was built with {}, the loaded model is {}", index.model.name, current.model.name,
+            ))),
+            None => Err(PotionError::ManifestMismatch("the loaded model has no manifest.json".to_string())),
+        }
+    }
+
+    pub fn config(&self) -> &ModelConfig {
+        &self.config
+    }
+
+    /* 
+        Primary function for converting a single sentence into an embedding.
+    */
+    pub fn encode(&self, sentence: &str) -> Vec<f32> {
+        self.try_encode(sentence).unwrap()
+    }
+
+    /*
+        Choose how words missing from the vocabulary are handled, `UnknownTokenPolicy::Skip` by default
+    */
+    pub fn with_unknown_token_policy(mut self, policy: UnknownTokenPolicy) -> Self {
+        self.unknown_tokens = policy;
+        self
+    }
+
+    /*
+        Like `encode`, but returns an error instead of panicking, e.g. `PotionError::UnknownToken`
+        when a word can't be built from the vocabulary and the policy is `UnknownTokenPolicy::Error`.
+    */
+    pub fn try_encode(&self, sentence: &str) -> Result<Vec<f32>, PotionError> {
+        self.try_encode_with_stats(sentence).map(|(embedding, _)| embedding)
+    }
+
+    /*
+        Like `try_encode`, also returning how many tokens were used and how many were unknown
+    */
+    pub fn try_encode_with_stats(&self, sentence: &str) -> Result<(Vec<f32>, EncodeStats), PotionError> {
+        self.try_encode_with(sentence, self.long_text)
+    }
+
+    /*
+        Choose how texts with many tokens are embedded, `LongText::Full` by default
+    */
+    pub fn with_long_text(mut self, long_text: LongText) -> Result<Self, PotionError> {
+        long_text.validate()?;
+        self.long_text = long_text;
+        Ok(self)
+    }
+
+    /*
+        Like `try_encode_with_stats`, handling long texts with `long_text` instead of the model's setting
+    */
+    pub fn try_encode_with(&self, sentence: &str, long_text: LongText) -> Result<(Vec<f32>, EncodeStats), PotionError> {
+        self.encode_text(sentence, None, long_text)
+    }
+
+    /*
+        Like `try_encode_with`, embedding `sentence` as a query or a document: the model's prefix for
+        `role` is put before it, and queries go through its query projection, if it has them
+    */
+    pub fn try_encode_as(&self, sentence: &str, role: Role, long_text: LongText) -> Result<(Vec<f32>, EncodeStats), PotionError> {
+        let prefix = match role {
+            Role::Query => self.config.query_prefix.as_deref(),
+            Role::Document => self.config.document_prefix.as_deref(),
+        };
+        match prefix {
+            Some(prefix) => self.encode_text(&format!("{}{}", prefix, sentence), Some(role), long_text),
+            None => self.encode_text(sentence, Some(role), long_text),
+        }
+    }
+
+    fn encode_text(&self, sentence: &str, role: Option<Role>, long_text: LongText) -> Result<(Vec<f32>, EncodeStats), PotionError> {
+        long_text.validate()?;
+        let query = role == Some(Role::Query);
+        let max_tokens = match long_text {
+            LongText::Truncate { max_tokens } => max_tokens,
+            LongText::Full | LongText::SlidingWindow { .. } => usize::MAX,
+        };
+        let (mut tokens, unknown_tokens) = setencen2tok(sentence, &self.tokenizer, &self.word_cache, self.whole_words, self.unk_id, self.unknown_tokens, max_tokens)?;
+        let truncated = tokens.len() > max_tokens;
+        tokens.truncate(max_tokens);
+
+        let (embedding, used) = match long_text {
+            LongText::SlidingWindow { window, stride } if tokens.len() > window => {
+                // Windows up to the one that reaches the end, each pooled and normalized on its own
+                let mut sum = vec![0.0f32; self.dimensions()];
+                let mut windows = 0;
+                let mut start = 0;
+                loop {
+                    let end = (start + window).min(tokens.len());
+                    if let (Some(embedding), _) = self.pool(&tokens[start..end], query) {
+                        for (s, x) in sum.iter_mut().zip(&self.normalized(embedding)) {
+                            *s += x;
+                        }
+                        windows += 1;
+                    }
+                    if end == tokens.len() {
+                        break;
+                    }
+                    start += stride;
+                }
+                for s in sum.iter_mut() {
+                    *s /= windows.max(1) as f32;
+                }
+                let used = tokens.iter().filter(|&&token| (token as usize) < self.embeddings.rows).count();
+                (Some(sum).filter(|_| windows > 0), used)
+            },
+            _ => self.pool(&tokens, query),
+        };
+        let stats = EncodeStats { tokens: used, unknown_tokens, truncated };
+
+        match embedding {
+            Some(embedding) => Ok((self.normalized(embedding), stats)),
+            None => Ok((vec![0.0f32; self.dimensions()], stats)),
+        }
+    }
+
+    /*
+        Weighted mean of the token rows, projected to the output dimensions, and how many tokens had a row.
+        None if no token had a row with a positive weight. `query` applies the query projection first.
+    */
+    fn pool(&self, tokens: &[u32], query: bool) -> (Option<Vec<f32>>, usize) {
+        let mut out_array = vec![0.0f32; self.dimensions];
+
+        // Sum the rows straight from the mapped table. Ids past its end (e.g. added tokens) have no embedding
+        let mut used = 0;
+        let mut total_weight = 0.0f32;
+        for &token in tokens {
+            let weight = self.token_weight(token);
+            if self.embeddings.add_row(token as usize, weight, &mut out_array) {
+                used += 1;
+                total_weight += weight;
+            }
+        }
+
+        if used == 0 || total_weight <= 0.0 {
+            return (None, used)
+        }
+
+        for x in out_array.iter_mut() {
+            *x /= total_weight;
+        }
+
+        if let Some(query_projection) = self.query_projection.as_ref().filter(|_| query) {
+            out_array = query_projection.apply(out_array);
+        }
+        if let Some(projection) = &self.projection {
+            out_array = projection.apply(out_array);
+        }
+        (Some(out_array), used)
+    }
+
+    fn normalized(&self, mut out_array: Vec<f32>) -> Vec<f32> {
+        if self.config.normalize {
+            let n = norm(&out_array);
+
+            if n > 0.0 {
+                for x in out_array.iter_mut() {
+                    *x /= n;
+                }
+            }
+        }
+        out_array
+    }
+
+    /*
+        Explain why `text` matches `query`: embeddings are weighted means of token vectors, so the cosine
+        similarity splits exactly into one term per token of the text (its vector against the query
+        embedding), and the same way for the query tokens against the text embedding. Terms are summed
+        per word and the `top_k` highest positive ones are returned for each side.
+        The query is embedded as `Role::Query` and the text as `Role::Document`, as search does, and the
+        similarity is a cosine even for models that don't normalize. The whole text is used, whatever the
+        `LongText` setting.
+     */
+    pub fn explain_match(&self, query: &str, text: &str, top_k: usize) -> Result<MatchExplanation, PotionError> {
+        // Embedded as search does: the query as `Role::Query` and the text as `Role::Document`,
+        // with the model's prefixes counted in the means but not listed as words
+        let words = |text: &str| words_with_tokens(text, &self.tokenizer, self.unk_id, self.unknown_tokens);
+        let prefix_words = |prefix: &Option<String>| prefix.as_deref().map_or(Ok(vec![]), words);
+        let all_tokens = |prefix: &[(String, String, Vec<u32>)], words: &[(String, String, Vec<u32>)]| prefix.iter().chain(words)
+            .flat_map(|(_, _, tokens)| tokens.iter().copied())
+            .collect::<Vec<u32>>();
+        let query_words = words(query)?;
+        let text_words = words(text)?;
+        let query_tokens = all_tokens(&prefix_words(&self.config.query_prefix)?, &query_words);
+        let text_tokens = all_tokens(&prefix_words(&self.config.document_prefix)?, &text_words);
+
+        let (query_pooled, text_pooled) = match (self.pool(&query_tokens, true).0, self.pool(&text_tokens, false).0) {
+            (Some(query_pooled), Some(text_pooled)) => (query_pooled, text_pooled),
+            _ => return Ok(MatchExplanation { similarity: 0.0, query_words: vec![], text_words: vec![] }),
+        };
+        // A cosine whether or not the model normalizes its embeddings
+        let unit = |v: &[f32]| {
+            let n = norm(v);
+            v.iter().map(|x| if n > 0.0 { x / n } else { 0.0 }).collect::<Vec<f32>>()
+        };
+        let query_embedding = unit(&query_pooled);
+        let text_embedding = unit(&text_pooled);
+        let similarity = query_embedding.iter().zip(&text_embedding).map(|(q, t)| q * t).sum();
+
+        // The projections are affine and the token weights of a mean add up to one, so projecting
+        // each row splits the projected mean exactly
+        let contributions = |words: &[(String, String, Vec<u32>)], tokens: &[u32], query: bool, pooled: &[f32], other: &[f32]| {
+            let total_weight: f32 = tokens.iter()
+                .filter(|&&token| (token as usize) < self.embeddings.rows)
+                .map(|&token| self.token_weight(token))
+                .sum();
+            let scale = norm(pooled);
+            let mut by_word: Vec<(String, String, f32)> = Vec::new();
+            for (original, word, tokens) in words {
+                let score: f32 = tokens.iter()
+                    .filter_map(|&token| {
+                        let mut row = self.embeddings.row(token as usize)?;
+                        if let Some(query_projection) = self.query_projection.as_ref().filter(|_| query) {
+                            row = query_projection.apply(row);
+                        }
+                        if let Some(projection) = &self.projection {
+                            row = projection.apply(row);
+                        }
+                        let dot: f32 = row.iter().zip(other).map(|(r, o)| r * o).sum();
+                        Some(self.token_weight(token) / total_weight * dot / scale)
+                    })
+                    .sum();
+                match by_word.iter_mut().find(|(_, key, _)| key == word) {
+                    Some((_, _, total)) => *total += score,
+                    None => by_word.push((original.clone(), word.clone(), score)),
+                }
+            }
+            by_word.retain(|(_, _, score)| *score > 0.0);
+            by_word.sort_by(|a, b| b.2.total_cmp(&a.2));
+            by_word.into_iter()
+                .take(top_k)
+                .map(|(word, _, score)| WordContribution { word, score })
+                .collect::<Vec<_>>()
+        };
+
+        Ok(MatchExplanation {
+            similarity,
+            query_words: contributions(&query_words, &query_tokens, true, &query_pooled, &text_embedding),
+            text_words: contributions(&text_words, &text_tokens, false, &text_pooled, &query_embedding),
+        })
+    }
+
+    /*
+        Collect the distinct words of `corpus` (as normalized by the tokenizer, e.g. lowercased) that
+        have a vocabulary token of their own and contain a letter, for `nearest_words`
+     */
+    pub fn corpus_words<'a>(&self, corpus: impl IntoIterator<Item = &'a str>) -> Result<CorpusWords, PotionError> {
+        let mut distinct = std::collections::HashSet::new();
+        for text in corpus {
+            pre_tokenize(text, &self.tokenizer, |word| {
+                if !distinct.contains(word) && word.chars().any(char::is_alphabetic) {
+                    distinct.insert(word.to_string());
+                }
+                Ok(())
+            })?;
+        }
+        let mut distinct: Vec<String> = distinct.into_iter().collect();
+        distinct.sort();
+
+        let model = self.tokenizer.get_model();
+        let mut words = Vec::new();
+        let mut vectors = Vec::new();
+        for word in distinct {
+            let token = match word2tok(&word, model).as_deref() {
+                Some(&[token]) if Some(token) != self.unk_id => token,
+                _ => continue,
+            };
+            if let Some(vector) = self.embeddings.row(token as usize) {
+                let vector = match &self.projection {
+                    Some(projection) => projection.apply(vector),
+                    None => vector,
+                };
+                let n = norm(&vector);
+                if n > 0.0 {
+                    vectors.extend(vector.iter().map(|x| x / n));
+                    words.push(word);
+                }
+            }
+        }
+        Ok(CorpusWords { words, vectors, dimensions: self.dimensions() })
+    }
+
+    /*
+        The `k` words of `corpus_words` closest to `term` by cosine similarity, closest first, leaving out
+        the term itself. `term` can be any text, e.g. "bleeding" -> "hemorrhage", "wound", ...
+     */
+    pub fn nearest_words(&self, term: &str, corpus_words: &CorpusWords, k: usize) -> Result<Vec<(String, f32)>, PotionError> {
+        let (pooled, _) = self.pool(&setencen2tok(term, &self.tokenizer, &self.word_cache, self.whole_words, self.unk_id, UnknownTokenPolicy::Skip, usize::MAX)?.0, false);
+        let query = match pooled {
+            Some(pooled) if corpus_words.dimensions == self.dimensions() => pooled,
+            Some(_) => return Err(PotionError::Config("corpus words were built with a different projection".to_string())),
+            None => return Ok(vec![]),
+        };
+        let n = norm(&query);
+        if n == 0.0 {
+            return Ok(vec![]);
+        }
+        let mut term_words = Vec::new();
+        pre_tokenize(term, &self.tokenizer, |word| {
+            term_words.push(word.to_string());
+            Ok(())
+        })?;
+
+        let mut scored: Vec<(usize, f32)> = corpus_words.vectors.chunks_exact(corpus_words.dimensions)
+            .enumerate()
+            .filter(|(i, _)| !term_words.contains(&corpus_words.words[*i]))
+            .map(|(i, vector)| (i, vector.iter().zip(&query).map(|(v, q)| v * q).sum::<f32>() / n))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(scored.into_iter()
+            .take(k)
+            .map(|(i, similarity)| (corpus_words.words[i].clone(), similarity))
+            .collect())
+    }
+
+    fn token_weight(&self, token: u32) -> f32 {
+        self.weights.as_ref().map_or(1.0, |weights| weights.get(token as usize).copied().unwrap_or(0.0))
+    }
+
+    /* 
+        Useful for converting multiple sentences into their respective embeddings.
+        Will use all available threads.
+    */
+    pub fn encode_many(&self, sentences: Vec<String>) -> Vec<Vec<f32>> {
+        sentences.par_iter() 
+        .map(|sentence| self.encode(sentence))
+        .collect()
+    }
+
+    pub fn try_encode_many(&self, sentences: Vec<String>) -> Result<Vec<Vec<f32>>, PotionError> {
+        self.try_encode_many_with(sentences, self.long_text)
+    }
+
+    pub fn try_encode_many_with(&self, sentences: Vec<String>, long_text: LongText) -> Result<Vec<Vec<f32>>, PotionError> {
+        sentences.par_iter()
+        .map(|sentence| self.try_encode_with(sentence, long_text).map(|(embedding, _)| embedding))
+        .collect()
+    }
+
+    pub fn try_encode_many_as(&self, sentences: Vec<String>, role: Role, long_text: LongText) -> Result<Vec<Vec<f32>>, PotionError> {
+        sentences.par_iter()
+        .map(|sentence| self.try_encode_as(sentence, role, long_text).map(|(embedding, _)| embedding))
+        .collect()
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
     use super::*;
 
     #[test]
     fn load_and_encode() {
-        let encoder = RustPotion::new(PotionModel::BASE2M, &Path::new("models/"));
+        let encoder = RustPotion::new(PotionModel::BASE2M, Path::new("models/"));
         assert_eq!(encoder.encode("test"), vec![
             -0.2448156, 0.061568744, -0.22705789, -0.31100106, 0.12387812, 0.012164102, 0.01942585, 0.035540108, 
             -0.07884298, -0.06828589, 0.050681777, 0.010798689, -0.24065527, 0.017179, -0.14778298, 0.017902775, 
@@ -386,7 +1767,469 @@ mod tests {
 
     #[test]
     fn zeroed() {
-        let encoder = RustPotion::new(PotionModel::BASE2M, &Path::new("models/"));
+        let encoder = RustPotion::new(PotionModel::BASE2M, Path::new("models/"));
         assert_eq!(encoder.encode(""), vec![0.0; 64]);
     }
+
+    /*
+        Writes a tiny 2 dimensional model with a BERT style tokenizer so the encoding paths
+        can be tested without downloading one
+    */
+    // Removed with everything in it when the returned TempDir is dropped
+    fn write_test_model(name: &str) -> tempfile::TempDir {
+        use safetensors::{Dtype, tensor::TensorView};
+        use tokenizers::models::wordpiece::WordPiece;
+        use tokenizers::normalizers::bert::BertNormalizer;
+        use tokenizers::pre_tokenizers::bert::BertPreTokenizer;
+
+        let dir = tempfile::Builder::new().prefix(&format!("rustpotion-{}-", name)).tempdir().unwrap();
+        let model_dir = dir.path().join(PotionModel::BASE2M.to_string());
+        std::fs::create_dir_all(&model_dir).unwrap();
+
+        let embeddings: Vec<f32> = vec![
+            5.0, 5.0,   // [UNK]
+            1.0, 0.0,   // hello
+            0.0, 1.0,   // world
+            1.0, 1.0,   // un
+            -1.0, 0.0,  // ##able
+            0.0, -1.0,  // ,
+            3.0, 4.0,   // привет
+            -3.0, 4.0,  // cafe
+        ];
+        let bytes: Vec<u8> = embeddings.iter().flat_map(|x| x.to_le_bytes()).collect();
+        let view = TensorView::new(Dtype::F32, vec![8, 2], &bytes).unwrap();
+        safetensors::serialize_to_file([("embeddings", view)], &None, &model_dir.join("model.safetensors")).unwrap();
+
+        // one token per line, the line number is the token id
+        let vocab_path = model_dir.join("vocab.txt");
+        std::fs::write(&vocab_path, "[UNK]\nhello\nworld\nun\n##able\n,\nпривет\ncafe\n").unwrap();
+        let model = WordPiece::from_file(vocab_path.to_str().unwrap()).unk_token("[UNK]".to_string()).build().unwrap();
+        let mut tokenizer = Tokenizer::new(model);
+        tokenizer.with_normalizer(Some(BertNormalizer::new(true, true, None, true)));
+        tokenizer.with_pre_tokenizer(Some(BertPreTokenizer));
+        tokenizer.save(model_dir.join("tokenizer.json"), false).unwrap();
+        dir
+    }
+
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn try_encode_tiny_model() {
+        let dir = write_test_model("encode");
+        let encoder = RustPotion::try_new(PotionModel::BASE2M, dir.path()).unwrap()
+            .with_unknown_token_policy(UnknownTokenPolicy::Error);
+        assert_close(&encoder.try_encode("Hello world").unwrap(), &[0.70710677, 0.70710677]);
+        assert_close(&encoder.try_encode("unable").unwrap(), &[0.0, 1.0]);
+        assert!(matches!(encoder.try_encode("unable xyz"), Err(PotionError::UnknownToken(w)) if w == "xyz"));
+        assert!(encoder.try_encode_many(vec!["hello".to_string(), "xyz".to_string()]).is_err());
+    }
+
+    #[test]
+    fn unknown_token_policies() {
+        let dir = write_test_model("unknown");
+        let encoder = RustPotion::try_new(PotionModel::BASE2M, dir.path()).unwrap();
+
+        // one stray symbol no longer fails the sentence
+        let (embedding, stats) = encoder.try_encode_with_stats("hello 🙂 world").unwrap();
+        assert_close(&embedding, &[0.70710677, 0.70710677]);
+        assert_eq!(stats, EncodeStats { tokens: 2, unknown_tokens: 1, truncated: false });
+
+        let (embedding, stats) = encoder.try_encode_with_stats("🙂").unwrap();
+        assert_eq!(embedding, vec![0.0; 2]);
+        assert_eq!(stats, EncodeStats { tokens: 0, unknown_tokens: 1, truncated: false });
+
+        let encoder = encoder.with_unknown_token_policy(UnknownTokenPolicy::Unk);
+        let (embedding, stats) = encoder.try_encode_with_stats("hello 🙂").unwrap();
+        // (1, 0) and [UNK] (5, 5)
+        assert_close(&embedding, &[0.7682213, 0.6401844]);
+        assert_eq!(stats, EncodeStats { tokens: 2, unknown_tokens: 1, truncated: false });
+    }
+
+    #[test]
+    fn load_from_dir_and_files() {
+        let dir = write_test_model("config");
+        let model_dir = dir.path().join("BASE2M");
+        assert_eq!(RustPotion::from_dir(&model_dir).unwrap().config(), &ModelConfig::default());
+
+        std::fs::write(model_dir.join("config.json"), r#"{"model_type": "model2vec", "apply_pca": 2, "hidden_dim": 2, "normalize": false}"#).unwrap();
+        let encoder = RustPotion::from_dir(&model_dir).unwrap();
+        assert_eq!(encoder.config(), &ModelConfig { normalize: false, apply_pca: Some(2), dimension: Some(2), ..ModelConfig::default() });
+        // mean of (1, 0) and (0, 1) without scaling to unit length
+        assert_close(&encoder.try_encode("hello world").unwrap(), &[0.5, 0.5]);
+
+        // a table narrower than apply_pca is accepted, since model2vec skipped that PCA, a wider one is rejected
+        std::fs::write(model_dir.join("config.json"), r#"{"apply_pca": 256}"#).unwrap();
+        assert_eq!(RustPotion::from_dir(&model_dir).unwrap().dimensions(), 2);
+        std::fs::write(model_dir.join("config.json"), r#"{"apply_pca": 1}"#).unwrap();
+        assert!(matches!(RustPotion::from_dir(&model_dir), Err(PotionError::Config(_))));
+
+        // renamed files, and a config that doesn't match the tensor
+        std::fs::rename(model_dir.join("model.safetensors"), dir.path().join("custom_model.safetensors")).unwrap();
+        std::fs::write(dir.path().join("custom_config.json"), r#"{"apply_pca": "auto", "dimension": 256}"#).unwrap();
+        let result = RustPotion::from_files(&dir.path().join("custom_model.safetensors"), &model_dir.join("tokenizer.json"), Some(&dir.path().join("custom_config.json")));
+        assert!(matches!(result, Err(PotionError::Config(_))));
+        let encoder = RustPotion::from_files(&dir.path().join("custom_model.safetensors"), &model_dir.join("tokenizer.json"), None).unwrap();
+        assert_eq!(encoder.dimensions(), 2);
+    }
+
+    #[test]
+    fn load_from_bytes() {
+        let dir = write_test_model("bytes");
+        let model_dir = dir.path().join("BASE2M");
+        let from_dir = RustPotion::from_dir(&model_dir).unwrap();
+        let safetensors = std::fs::read(model_dir.join("model.safetensors")).unwrap();
+        let tokenizer = std::fs::read(model_dir.join("tokenizer.json")).unwrap();
+
+        let from_bytes = RustPotion::from_bytes(safetensors.clone(), &tokenizer, Some(br#"{"normalize": false}"#)).unwrap();
+        assert!(!from_bytes.config().normalize);
+        assert_eq!(from_bytes.encode("hello world"), vec![0.5, 0.5]);
+
+        let from_static = RustPotion::from_static(safetensors.leak(), &tokenizer, None).unwrap();
+        assert_eq!(from_static.encode("Hello, world!"), from_dir.encode("Hello, world!"));
+
+        assert!(matches!(RustPotion::from_bytes(vec![1, 2, 3], &tokenizer, None), Err(PotionError::Safetensors(_))));
+        assert!(matches!(RustPotion::from_bytes(std::fs::read(model_dir.join("model.safetensors")).unwrap(), b"{", None), Err(PotionError::TokenizerParse(_))));
+    }
+
+    #[test]
+    fn query_and_document_roles() {
+        use safetensors::tensor::TensorView;
+
+        let dir = write_test_model("roles");
+        let model_dir = dir.path().join("BASE2M");
+        // swaps the two dimensions and adds 1 to the first
+        let weight: Vec<u8> = [0.0f32, 1.0, 1.0, 0.0].iter().flat_map(|x| x.to_le_bytes()).collect();
+        let bias: Vec<u8> = [1.0f32, 0.0].iter().flat_map(|x| x.to_le_bytes()).collect();
+        safetensors::serialize_to_file([
+            ("weight", TensorView::new(Dtype::F32, vec![2, 2], &weight).unwrap()),
+            ("bias", TensorView::new(Dtype::F32, vec![2], &bias).unwrap()),
+        ], &None, &model_dir.join("query_projection.safetensors")).unwrap();
+        std::fs::write(model_dir.join("config.json"), r#"{"normalize": false, "prompts": {"query": "hello ", "passage": "cafe "}, "query_projection": "query_projection.safetensors"}"#).unwrap();
+
+        let model = RustPotion::from_dir(&model_dir).unwrap();
+        assert_eq!(model.config().query_prefix.as_deref(), Some("hello "));
+        assert_eq!(model.config().document_prefix.as_deref(), Some("cafe "));
+        // "hello world" pooled to (0.5, 0.5), then projected
+        let (query, _) = model.try_encode_as("world", Role::Query, LongText::default()).unwrap();
+        assert_close(&query, &[1.5, 0.5]);
+        // "cafe hello", not projected
+        let (document, _) = model.try_encode_as("hello", Role::Document, LongText::default()).unwrap();
+        assert_close(&document, &[-1.0, 2.0]);
+        assert_eq!(model.try_encode_many_as(vec!["hello".to_string()], Role::Document, LongText::default()).unwrap(), vec![document]);
+        // Without a role texts are embedded as they are
+        assert_close(&model.try_encode("world").unwrap(), &[0.0, 1.0]);
+
+        // Explanations score the query and the text the same way, as a cosine although the model doesn't normalize
+        let cosine = |a: &[f32], b: &[f32]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>() / (norm(a) * norm(b));
+        let (text, _) = model.try_encode_as("hello world", Role::Document, LongText::default()).unwrap();
+        let explanation = model.explain_match("world", "hello world", 10).unwrap();
+        assert!((explanation.similarity - cosine(&query, &text)).abs() < 1e-6);
+        // The prefixes count towards it but aren't words of the query or text
+        assert!(explanation.query_words.iter().chain(&explanation.text_words).all(|w| w.word == "hello" || w.word == "world"));
+        assert!(!explanation.text_words.is_empty());
+
+        // The projection has to map the model's width onto itself
+        let weight: Vec<u8> = [1.0f32, 0.0].iter().flat_map(|x| x.to_le_bytes()).collect();
+        safetensors::serialize_to_file([("weight", TensorView::new(Dtype::F32, vec![1, 2], &weight).unwrap())], &None, &model_dir.join("query_projection.safetensors")).unwrap();
+        assert!(matches!(RustPotion::from_dir(&model_dir), Err(PotionError::MissingTensor(_))));
+    }
+
+    #[test]
+    fn manifest_verification() {
+        let dir = write_test_model("manifest");
+        let model_dir = dir.path().join("BASE2M");
+        assert!(RustPotion::from_dir(&model_dir).unwrap().manifest().is_none());
+
+        let manifest = ModelManifest::create(&model_dir, "tiny").unwrap();
+        assert_eq!((manifest.dimensions, manifest.vocab_size), (2, 8));
+        assert_eq!(manifest.files.keys().collect::<Vec<_>>(), vec!["model.safetensors", "tokenizer.json"]);
+        manifest.save(&model_dir.join("manifest.json")).unwrap();
+        let model = RustPotion::from_dir(&model_dir).unwrap();
+        assert_eq!(model.manifest(), Some(&manifest));
+
+        // Vectors recorded for an index only match the same model at the same size
+        let index = model.index_manifest().unwrap();
+        index.save(&dir.path().join("index.json")).unwrap();
+        let index = IndexManifest::load(&dir.path().join("index.json")).unwrap();
+        model.check_index(&index).unwrap();
+        let truncated = RustPotion::from_dir(&model_dir).unwrap().with_truncation(1).unwrap();
+        assert!(matches!(truncated.check_index(&index), Err(PotionError::ManifestMismatch(_))));
+        let renamed = IndexManifest { model: ModelManifest { name: "other".to_string(), ..manifest.clone() }, dimensions: 2 };
+        assert!(matches!(model.check_index(&renamed), Err(PotionError::ManifestMismatch(_))));
+
+        // A truncated table fails at load time
+        let table = std::fs::read(model_dir.join("model.safetensors")).unwrap();
+        std::fs::write(model_dir.join("model.safetensors"), &table[..table.len() - 4]).unwrap();
+        assert!(matches!(RustPotion::from_dir(&model_dir), Err(PotionError::ManifestMismatch(_))));
+        std::fs::write(model_dir.join("model.safetensors"), &table).unwrap();
+
+        // So does a manifest for a different model
+        ModelManifest { vocab_size: 9, ..manifest }.save(&model_dir.join("manifest.json")).unwrap();
+        assert!(matches!(RustPotion::from_dir(&model_dir), Err(PotionError::ManifestMismatch(_))));
+    }
+
+    #[test]
+    fn fingerprints() {
+        let dir = write_test_model("fingerprint");
+        let model_dir = dir.path().join("BASE2M");
+        let fingerprint = RustPotion::from_dir(&model_dir).unwrap().fingerprint();
+        assert_eq!(RustPotion::from_dir(&model_dir).unwrap().fingerprint(), fingerprint);
+        assert_ne!(RustPotion::from_dir(&model_dir).unwrap().with_truncation(1).unwrap().fingerprint(), fingerprint);
+
+        // Another table in the same place
+        let table_path = model_dir.join("model.safetensors");
+        let mut table = std::fs::read(&table_path).unwrap();
+        let last = table.len() - 1;
+        table[last] ^= 1;
+        std::fs::write(&table_path, &table).unwrap();
+        assert_ne!(RustPotion::from_dir(&model_dir).unwrap().fingerprint(), fingerprint);
+    }
+
+    #[test]
+    fn manifest_checked_every_load() {
+        let dir = write_test_model("verified");
+        let model_dir = dir.path().join("BASE2M");
+        ModelManifest::create(&model_dir, "tiny").unwrap().save(&model_dir.join("manifest.json")).unwrap();
+        RustPotion::from_dir(&model_dir).unwrap();
+
+        // A table changed in place with its size and time kept is still caught
+        let table_path = model_dir.join("model.safetensors");
+        let modified = std::fs::metadata(&table_path).unwrap().modified().unwrap();
+        let mut table = std::fs::read(&table_path).unwrap();
+        let last = table.len() - 1;
+        table[last] ^= 1;
+        std::fs::write(&table_path, &table).unwrap();
+        std::fs::File::options().write(true).open(&table_path).unwrap().set_modified(modified).unwrap();
+        assert!(matches!(RustPotion::from_dir(&model_dir), Err(PotionError::ManifestMismatch(_))));
+        assert!(!model_dir.join("manifest.verified.json").exists());
+    }
+
+    #[test]
+    fn long_texts() {
+        let dir = write_test_model("long");
+        let encoder = RustPotion::from_dir(&dir.path().join("BASE2M")).unwrap();
+        let text = "hello hello hello world";
+        let n = 10f32.sqrt();
+        assert_close(&encoder.encode(text), &[3.0 / n, 1.0 / n]);
+
+        // Tokens past the budget are left out
+        let (embedding, stats) = encoder.try_encode_with(text, LongText::Truncate { max_tokens: 2 }).unwrap();
+        assert_eq!(embedding, vec![1.0, 0.0]);
+        assert_eq!(stats, EncodeStats { tokens: 2, unknown_tokens: 0, truncated: true });
+        let (_, stats) = encoder.try_encode_with(text, LongText::Truncate { max_tokens: 4 }).unwrap();
+        assert!(!stats.truncated);
+        // "unable" is two tokens, only the first fits
+        let (embedding, _) = encoder.try_encode_with("world unable", LongText::Truncate { max_tokens: 2 }).unwrap();
+        let n = 1.25f32.sqrt();
+        assert_close(&embedding, &[0.5 / n, 1.0 / n]);
+
+        // Windows (hello hello) and (hello world) count the same
+        let h = std::f32::consts::FRAC_1_SQRT_2;
+        let encoder = encoder.with_long_text(LongText::SlidingWindow { window: 2, stride: 2 }).unwrap();
+        let (embedding, stats) = encoder.try_encode_with_stats(text).unwrap();
+        let window_mean = [(1.0 + h) / 2.0, h / 2.0];
+        let n = (window_mean[0] * window_mean[0] + window_mean[1] * window_mean[1]).sqrt();
+        assert_close(&embedding, &[window_mean[0] / n, window_mean[1] / n]);
+        assert_eq!(stats.tokens, 4);
+        // Short texts are a single window
+        assert_eq!(encoder.encode("hello world"), RustPotion::from_dir(&dir.path().join("BASE2M")).unwrap().encode("hello world"));
+        assert_eq!(encoder.encode_many(vec![text.to_string()]), vec![embedding]);
+
+        assert!(encoder.try_encode_with(text, LongText::Truncate { max_tokens: 0 }).is_err());
+        assert!(encoder.with_long_text(LongText::SlidingWindow { window: 2, stride: 3 }).is_err());
+    }
+
+    #[test]
+    fn explain_matches() {
+        let dir = write_test_model("explain");
+        let encoder = RustPotion::from_dir(&dir.path().join("BASE2M")).unwrap();
+
+        // Text embedding is the mean of (1, 0), (0, 1), (0, -1), (3, 4), (1, 0) = (1, 0.8)
+        let explanation = encoder.explain_match("hello", "Hello world, Привет hello", 10).unwrap();
+        let n = 1.64f32.sqrt();
+        let similarity: f32 = encoder.encode("hello").iter().zip(encoder.encode("Hello world, Привет hello")).map(|(q, t)| q * t).sum();
+        assert!((explanation.similarity - similarity).abs() < 1e-6);
+        // Words are reported as written, repeated words are summed, zero contributions left out
+        let text_words: Vec<&str> = explanation.text_words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(text_words, vec!["Привет", "Hello"]);
+        assert_close(&explanation.text_words.iter().map(|w| w.score).collect::<Vec<_>>(), &[3.0 / 5.0 / n, 2.0 / 5.0 / n]);
+        assert_eq!(explanation.query_words, vec![WordContribution { word: "hello".to_string(), score: explanation.similarity }]);
+        // Every token's term adds up to the similarity
+        let total: f32 = explanation.text_words.iter().map(|w| w.score).sum();
+        assert!((total - explanation.similarity).abs() < 1e-5);
+
+        assert_eq!(encoder.explain_match("hello", "Hello world, Привет hello", 1).unwrap().text_words.len(), 1);
+        assert_eq!(encoder.explain_match("hello", "", 10).unwrap().similarity, 0.0);
+    }
+
+    #[test]
+    fn nearest_corpus_words() {
+        let dir = write_test_model("nearest");
+        let encoder = RustPotion::from_dir(&dir.path().join("BASE2M")).unwrap();
+
+        // "unable" is two tokens and "," has no letter, so neither is a candidate. "café" is normalized to "cafe"
+        let corpus_words = encoder.corpus_words(["Hello world, unable", "Привет café"]).unwrap();
+        assert_eq!(corpus_words.len(), 4);
+
+        // Rows: hello (1, 0), world (0, 1), привет (3, 4), cafe (-3, 4)
+        let nearest = encoder.nearest_words("hello", &corpus_words, 5).unwrap();
+        let words: Vec<&str> = nearest.iter().map(|(word, _)| word.as_str()).collect();
+        assert_eq!(words, vec!["привет", "world", "cafe"]);
+        assert_close(&nearest.iter().map(|(_, similarity)| *similarity).collect::<Vec<_>>(), &[0.6, 0.0, -0.6]);
+        assert_eq!(encoder.nearest_words("Hello", &corpus_words, 1).unwrap().len(), 1);
+        assert!(encoder.nearest_words("", &corpus_words, 5).unwrap().is_empty());
+
+        let truncated = RustPotion::from_dir(&dir.path().join("BASE2M")).unwrap().with_truncation(1).unwrap();
+        assert!(truncated.nearest_words("hello", &corpus_words, 5).is_err());
+    }
+
+    #[test]
+    fn quantized_tables() {
+        let dir = write_test_model("quantize");
+        let model_dir = dir.path().join("BASE2M");
+        let reference = RustPotion::from_dir(&model_dir).unwrap();
+
+        for (quantization, tolerance) in [(Quantization::F16, 1e-3), (Quantization::BF16, 1e-2), (Quantization::Int8, 2e-2)] {
+            let quantized_dir = dir.path().join(format!("{:?}", quantization));
+            std::fs::create_dir_all(&quantized_dir).unwrap();
+            std::fs::copy(model_dir.join("tokenizer.json"), quantized_dir.join("tokenizer.json")).unwrap();
+            quantize_embeddings(&model_dir.join("model.safetensors"), &quantized_dir.join("model.safetensors"), quantization).unwrap();
+
+            let encoder = RustPotion::from_dir(&quantized_dir).unwrap();
+            for text in ["hello world", "unable", "Привет, cafe"] {
+                let expected = reference.encode(text);
+                let actual = encoder.encode(text);
+                assert!(expected.iter().zip(&actual).all(|(e, a)| (e - a).abs() < tolerance), "{:?}: {:?} != {:?}", quantization, actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn reduced_dimensions() {
+        use safetensors::tensor::TensorView;
+
+        let dir = write_test_model("reduce");
+        let model_dir = dir.path().join("BASE2M");
+        let encoder = RustPotion::from_dir(&model_dir).unwrap().with_truncation(1).unwrap();
+        assert_eq!(encoder.dimensions(), 1);
+        // (3, 4) truncated to (3) and normalized again
+        assert_eq!(encoder.encode("привет"), vec![1.0]);
+        assert_eq!(encoder.encode_many(vec!["cafe".to_string(), "".to_string()]), vec![vec![-1.0], vec![0.0]]);
+        assert!(RustPotion::from_dir(&model_dir).unwrap().with_truncation(3).is_err());
+
+        // project onto (x - y) / sqrt(2) and (x + y) / sqrt(2), after subtracting a mean of (1, 0)
+        let h = std::f32::consts::FRAC_1_SQRT_2;
+        let components: Vec<u8> = [h, -h, h, h].iter().flat_map(|x| x.to_le_bytes()).collect();
+        let mean: Vec<u8> = [1.0f32, 0.0].iter().flat_map(|x| x.to_le_bytes()).collect();
+        safetensors::serialize_to_file([
+            ("components", TensorView::new(Dtype::F32, vec![2, 2], &components).unwrap()),
+            ("mean", TensorView::new(Dtype::F32, vec![2], &mean).unwrap()),
+        ], &None, &model_dir.join("pca.safetensors")).unwrap();
+
+        let encoder = RustPotion::from_dir(&model_dir).unwrap()
+            .with_pca(&model_dir.join("pca.safetensors"), Some(1)).unwrap();
+        assert_eq!(encoder.dimensions(), 1);
+        // world: (0, 1) - (1, 0) = (-1, 1) projects to -sqrt(2), normalized to -1
+        assert_close(&encoder.encode("world"), &[-1.0]);
+        let encoder = RustPotion::from_dir(&model_dir).unwrap()
+            .with_pca(&model_dir.join("pca.safetensors"), None).unwrap();
+        assert_close(&encoder.encode("world"), &[-1.0, 0.0]);
+        assert!(RustPotion::from_dir(&model_dir).unwrap().with_pca(&model_dir.join("pca.safetensors"), Some(3)).is_err());
+    }
+
+    #[test]
+    fn token_weights() {
+        let dir = write_test_model("weights");
+        let model_dir = dir.path().join("BASE2M");
+        let encoder = RustPotion::from_dir(&model_dir).unwrap();
+        assert!(encoder.token_weights().is_none());
+
+        // "hello" occurs in every document, "world" in one of three
+        let corpus = ["hello world", "hello", "hello hello"];
+        let idf = encoder.compute_token_weights(corpus, TokenWeighting::Idf).unwrap();
+        assert_eq!(idf.len(), 8);
+        assert_close(&idf[1..3], &[1.0, 2f32.ln() + 1.0]);
+        let sif = encoder.compute_token_weights(corpus, TokenWeighting::Sif { a: 1.0 }).unwrap();
+        assert_close(&sif[1..4], &[1.0 / (1.0 + 0.8), 1.0 / (1.0 + 0.2), 1.0]);
+
+        // Weighted mean of (1, 0) and (0, 1) with weights 1 and 3
+        let weights = vec![0.0, 1.0, 3.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        save_token_weights(&weights, &model_dir.join("weights.safetensors")).unwrap();
+        let loaded = load_token_weights(&model_dir.join("weights.safetensors")).unwrap();
+        assert_eq!(loaded, weights);
+        let weighted = encoder.with_token_weights(Some(loaded)).unwrap();
+        let n = 10f32.sqrt();
+        assert_close(&weighted.encode("hello world"), &[1.0 / n, 3.0 / n]);
+        // Only zero weights left, same as no tokens
+        assert_eq!(weighted.encode("un"), vec![0.0, 0.0]);
+        assert!(weighted.with_token_weights(Some(vec![1.0; 3])).is_err());
+
+        // Weights stored in the model file are used by default
+        let mut tensors = std::fs::read(model_dir.join("model.safetensors")).unwrap();
+        let embeddings: Vec<u8> = {
+            let tensors = SafeTensors::deserialize(&tensors).unwrap();
+            tensors.tensor("embeddings").unwrap().data().to_vec()
+        };
+        let weight_bytes: Vec<u8> = weights.iter().flat_map(|w| w.to_le_bytes()).collect();
+        tensors = safetensors::serialize([
+            ("embeddings", safetensors::tensor::TensorView::new(Dtype::F32, vec![8, 2], &embeddings).unwrap()),
+            ("weights", safetensors::tensor::TensorView::new(Dtype::F32, vec![8], &weight_bytes).unwrap()),
+        ], &None).unwrap();
+        std::fs::write(model_dir.join("model.safetensors"), tensors).unwrap();
+        let encoder = RustPotion::from_dir(&model_dir).unwrap();
+        assert_close(&encoder.encode("hello world"), &[1.0 / n, 3.0 / n]);
+        let h = std::f32::consts::FRAC_1_SQRT_2;
+        assert_close(&encoder.with_token_weights(None).unwrap().encode("hello world"), &[h, h]);
+    }
+
+    #[test]
+    fn cached_whole_words() {
+        let dir = write_test_model("cache");
+        let tokenizer = load_tokenizer(&dir.path().join("BASE2M").join("tokenizer.json")).unwrap();
+        assert!(splits_on_whitespace(&tokenizer));
+        let unk_id = unk_token_id(&tokenizer);
+
+        // Raw words that the normalizer and pre-tokenizer split further or join
+        let text = "Hello,world  unable\tПРИВЕТ café\u{85}hello x\u{b}y 🙂";
+        for policy in [UnknownTokenPolicy::Skip, UnknownTokenPolicy::Unk] {
+            let cache = WordCache::default();
+            let whole_words = setencen2tok(text, &tokenizer, &cache, true, unk_id, policy, usize::MAX).unwrap();
+            let pre_tokenized = setencen2tok(text, &tokenizer, &WordCache::default(), false, unk_id, policy, usize::MAX).unwrap();
+            assert_eq!(whole_words, pre_tokenized);
+            // Second pass is served from the cache
+            assert_eq!(setencen2tok(text, &tokenizer, &cache, true, unk_id, policy, usize::MAX).unwrap(), whole_words);
+            assert!(cache.words.read().unwrap().contains_key("Hello,world"));
+            // Both stop soon after the budget, keeping the same first tokens
+            let (budget, _) = setencen2tok(text, &tokenizer, &cache, true, unk_id, policy, 2).unwrap();
+            let (pre_tokenized_budget, _) = setencen2tok(text, &tokenizer, &WordCache::default(), false, unk_id, policy, 2).unwrap();
+            assert_eq!(budget, pre_tokenized_budget);
+            assert!(budget.len() > 2 && budget.len() < whole_words.0.len());
+            assert_eq!(budget[..], whole_words.0[..budget.len()]);
+        }
+    }
+
+    #[test]
+    fn non_ascii_text() {
+        let dir = write_test_model("unicode");
+        let encoder = RustPotion::try_new(PotionModel::BASE2M, dir.path()).unwrap();
+        // Cyrillic is lowercased rather than dropped and accents are stripped by the BERT normalizer
+        assert_close(&encoder.try_encode("ПРИВЕТ").unwrap(), &[0.6, 0.8]);
+        assert_close(&encoder.try_encode("Café").unwrap(), &[-0.6, 0.8]);
+        assert_eq!(encoder.try_encode("").unwrap(), vec![0.0; 2]);
+    }
+
+    #[test]
+    fn try_new_reports_errors() {
+        let dir = tempfile::Builder::new().prefix("rustpotion-missing-").tempdir().unwrap();
+        assert!(matches!(RustPotion::try_new(PotionModel::BASE2M, dir.path()), Err(PotionError::Io(_))));
+
+        let dir = write_test_model("bad-tokenizer");
+        std::fs::write(dir.path().join("BASE2M/tokenizer.json"), "{").unwrap();
+        assert!(matches!(RustPotion::try_new(PotionModel::BASE2M, dir.path()), Err(PotionError::TokenizerParse(_))));
+    }
 }
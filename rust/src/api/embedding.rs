@@ -0,0 +1,129 @@
+use crate::backends;
+use flutter_rust_bridge::frb;
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// What a text is embedded as. Retrieval models such as bge and e5 prefix
+/// queries and documents differently, and some project queries on their own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingRole {
+    Query,
+    Document,
+}
+
+/// Loads the model in `model_path` as `model_id` with the engine its files are
+/// for: a model2vec directory (model.safetensors, tokenizer.json) runs on
+/// RustPotion, a sentence-transformer directory (model.onnx, tokenizer.json) on
+/// ONNX Runtime when the app is built with the onnx feature.
+#[frb(sync)]
+pub fn init_embedding_model(model_id: String, model_path: String) -> Result<bool> {
+    let model_path = PathBuf::from(&model_path);
+    println!("Initializing embedding model '{}' with model path: {:?}", model_id, model_path);
+    backends::set_backend(&model_id, false, || backends::open_backend(&model_id, &model_path))
+}
+
+/// Loads `model_path` and puts it in place of whatever `model_id` was, even
+/// if it needs a different engine. The old model keeps serving calls already running.
+#[frb(sync)]
+pub fn swap_embedding_model(model_id: String, model_path: String) -> Result<bool> {
+    let model_path = PathBuf::from(&model_path);
+    println!("Swapping embedding model '{}' to model path: {:?}", model_id, model_path);
+    backends::set_backend(&model_id, true, || backends::open_backend(&model_id, &model_path))
+}
+
+/// Drops `model_id`, false if it wasn't loaded
+#[frb(sync)]
+pub fn unload_embedding_model(model_id: String) -> bool {
+    backends::unload_backend(&model_id)
+}
+
+/// Ids of the models that are currently loaded, whatever engine runs them
+#[frb(sync)]
+pub fn loaded_embedding_models() -> Vec<String> {
+    backends::loaded_backends().iter()
+        .map(|backend| backend.model_id().to_string())
+        .collect()
+}
+
+/// Length of the embeddings `model_id` returns
+#[frb(sync)]
+pub fn embedding_dimensions(model_id: String) -> Result<i32> {
+    Ok(backends::get_backend(&model_id)?.dimensions() as i32)
+}
+
+/// Embeds `text` as a search query or as a document to index, see `EmbeddingRole`,
+/// reusing what the embedding cache holds for it like `get_embeddings`
+#[frb(sync)]
+pub fn get_embedding(model_id: String, text: String, role: EmbeddingRole) -> Result<Vec<f64>> {
+    let backend = backends::get_backend(&model_id)?;
+
+    Ok(backends::cache::embed_cached(backend.as_ref(), text, role)?
+        .into_iter()
+        .map(|x| x as f64)
+        .collect())
+}
+
+/// Embeds `texts` as `role`, reusing what the embedding cache holds for them
+/// once `init_embedding_cache` opened it
+#[frb(sync)]
+pub fn get_embeddings(model_id: String, texts: Vec<String>, role: EmbeddingRole) -> Result<Vec<Vec<f64>>> {
+    let backend = backends::get_backend(&model_id)?;
+
+    Ok(backends::cache::embed_batch_cached(backend.as_ref(), texts, role)?
+        .into_iter()
+        .map(|vec| vec.into_iter().map(|x| x as f64).collect())
+        .collect())
+}
+
+#[derive(Debug, Clone)]
+pub struct EmbeddingCacheStats {
+    // Texts found in and missing from the cache since it was opened
+    pub hits: i64,
+    pub misses: i64,
+    // What it holds on disk
+    pub entries: i64,
+    pub bytes: i64,
+}
+
+/// Keeps the embeddings `get_embedding(s)` computes in `cache_dir`, at most
+/// `max_megabytes` of them, so re-embedding an unchanged text reads it back.
+/// Least recently used embeddings go first. 0 megabytes turns the cache off.
+/// Model ids have to change when their model does (`swap_embedding_model`
+/// drops what the old one cached).
+#[frb(sync)]
+pub fn init_embedding_cache(cache_dir: String, max_megabytes: i32) -> Result<bool> {
+    if max_megabytes <= 0 {
+        println!("Embedding cache off");
+        backends::cache::set_cache(None)?;
+        return Ok(false);
+    }
+    println!("Opening embedding cache in {:?}, up to {} MB", cache_dir, max_megabytes);
+    backends::cache::set_cache(Some((&PathBuf::from(&cache_dir), max_megabytes as u64 * 1024 * 1024)))?;
+    Ok(true)
+}
+
+/// Hits, misses and size of the embedding cache, all 0 when it is off
+#[frb(sync)]
+pub fn embedding_cache_stats() -> EmbeddingCacheStats {
+    let stats = backends::cache::cache()
+        .map(|cache| cache.stats())
+        .unwrap_or_default();
+    EmbeddingCacheStats {
+        hits: stats.hits as i64,
+        misses: stats.misses as i64,
+        entries: stats.entries as i64,
+        bytes: stats.bytes as i64,
+    }
+}
+
+/// Deletes every cached embedding
+#[frb(sync)]
+pub fn clear_embedding_cache() -> Result<bool> {
+    match backends::cache::cache() {
+        Some(cache) => {
+            cache.clear()?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
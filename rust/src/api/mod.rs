@@ -3,8 +3,12 @@ pub mod tokenizer;
 pub mod pdf_text_extractor;
 pub mod text_splitter;
 //pub mod rustpotion;
-//pub mod ort_functions;
 //pub mod fast_embed; Don't delete
+#[cfg(feature = "rustpotion")]
 pub mod rustpotion;
+pub mod embedding;
+#[cfg(feature = "onnx")]
+pub mod ort_functions;
+pub mod rerank;
 pub mod monolith_functions;
 pub mod fast_html2md_functions;
\ No newline at end of file
@@ -124,4 +124,89 @@ pub fn extract_text_from_pdf_extract_multithreaded(pdf_bytes: Vec<u8>) -> Result
     //std::process::exit(0);
     
     result
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone)]
+pub struct PdfExtractionReport {
+    pub pages: Vec<TextWithLocation>,
+    pub total_chars: i64,
+    pub undecodable_chars: i64,
+    // Share of glyphs that could not be mapped to text, e.g. Type3 or symbolic fonts without ToUnicode
+    pub undecodable_fraction: f64,
+    // Pages drawn as vector outlines without text operators; these need rasterizing and OCR
+    pub outlined_text_pages: Vec<i32>,
+}
+
+#[flutter_rust_bridge::frb(sync)]
+pub fn extract_text_from_pdf_with_report(pdf_bytes: Vec<u8>) -> Result<PdfExtractionReport> {
+    let start = Instant::now();
+
+    let (pages, report) = pdf_extract::extract_text_from_mem_by_pages_multithreaded_with_report(&pdf_bytes)
+        .map_err(|e| anyhow::anyhow!("PDF extraction error: {}", e))?;
+    let decode = report.decode();
+
+    println!("PDF extraction with report took: {:?}, undecodable: {}/{} chars",
+        start.elapsed(), decode.undecodable_chars, decode.total_chars);
+
+    Ok(PdfExtractionReport {
+        pages: pages.into_iter()
+            .zip(report.pages.iter())
+            .map(|(text, page)| TextWithLocation {
+                text,
+                page_number: page.page_num as i32,
+            })
+            .collect(),
+        total_chars: decode.total_chars as i64,
+        undecodable_chars: decode.undecodable_chars as i64,
+        undecodable_fraction: decode.undecodable_fraction(),
+        outlined_text_pages: report.outlined_text_pages().into_iter().map(|p| p as i32).collect(),
+    })
+}
+
+/// Words of one page grouped into blocks and lines, as the JSON produced by
+/// `pdf_extract::LayoutPage::to_json`. Boxes are in PDF points with the origin
+/// at the top left of the displayed page so the viewer only has to scale them.
+#[flutter_rust_bridge::frb(sync)]
+pub fn extract_page_layout_json(pdf_bytes: Vec<u8>, page_number: i32) -> Result<String> {
+    let page = pdf_extract::extract_layout_from_mem_by_page(&pdf_bytes, page_number as u32)
+        .map_err(|e| anyhow::anyhow!("PDF layout extraction error: {}", e))?;
+    Ok(page.to_json())
+}
+
+#[derive(Debug, Clone)]
+pub struct HighlightRect {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct PageHighlights {
+    pub page_number: i32,
+    // Size of the displayed page in PDF points, to scale the rects to the viewer
+    pub page_width: f64,
+    pub page_height: f64,
+    pub rects: Vec<HighlightRect>,
+}
+
+/// Finds where a search hit appears on a page. Each entry of `needles` is matched
+/// on its own, so pass the whole snippet for a phrase hit or the individual query
+/// terms for a keyword hit. Case, whitespace, punctuation and hyphenation are ignored.
+#[flutter_rust_bridge::frb(sync)]
+pub fn find_text_highlights(pdf_bytes: Vec<u8>, page_number: i32, needles: Vec<String>) -> Result<PageHighlights> {
+    let page = pdf_extract::extract_layout_from_mem_by_page(&pdf_bytes, page_number as u32)
+        .map_err(|e| anyhow::anyhow!("PDF layout extraction error: {}", e))?;
+
+    let rects = needles.iter()
+        .flat_map(|needle| page.find_text(needle))
+        .map(|b| HighlightRect { left: b.x0, top: b.y0, right: b.x1, bottom: b.y1 })
+        .collect();
+
+    Ok(PageHighlights {
+        page_number,
+        page_width: page.width,
+        page_height: page.height,
+        rects,
+    })
+}
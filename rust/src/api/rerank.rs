@@ -0,0 +1,131 @@
+#[cfg(feature = "onnx")]
+use crate::backends::cross_encoder::CrossEncoder;
+use flutter_rust_bridge::frb;
+use anyhow::Result;
+use std::path::PathBuf;
+#[cfg(feature = "onnx")]
+use std::sync::{Arc, RwLock};
+#[cfg(feature = "onnx")]
+use std::time::{Duration, Instant};
+
+// Candidates scored when `rerank` isn't told how many, so its latency doesn't
+// grow with the number of search results
+const DEFAULT_TOP_N: usize = 20;
+
+// The loaded cross-encoder, if any. Calls clone the Arc, so loading another
+// one never pulls it away from a reranking that is still running.
+#[cfg(feature = "onnx")]
+static RERANKER: RwLock<Option<Arc<CrossEncoder>>> = RwLock::new(None);
+
+#[derive(Debug, Clone)]
+pub struct RerankedCandidate {
+    // Position in the candidates given to `rerank`
+    pub index: i32,
+    // Probability that it answers the query, None when it wasn't scored: no
+    // reranker is loaded, it is past the top N, or the budget ran out first
+    pub score: Option<f64>,
+}
+
+/// Loads the cross-encoder in `model_path` (model.onnx and tokenizer.json,
+/// e.g. ms-marco-MiniLM-L-6-v2) for `rerank`. Returns false when there is no
+/// model there or the app is built without the onnx feature, and `rerank`
+/// then keeps results in their original order.
+#[frb(sync)]
+pub fn init_reranker(model_path: String) -> Result<bool> {
+    let model_path = PathBuf::from(&model_path);
+    if !model_path.join("model.onnx").exists() {
+        println!("No reranker model in {:?}, results keep their order", model_path);
+        return Ok(false);
+    }
+
+    #[cfg(feature = "onnx")]
+    {
+        println!("Loading reranker from: {:?}", model_path);
+        let reranker = CrossEncoder::from_dir(&model_path)?;
+        *RERANKER.write().unwrap() = Some(Arc::new(reranker));
+        Ok(true)
+    }
+    #[cfg(not(feature = "onnx"))]
+    {
+        println!("Built without the onnx feature, results keep their order");
+        Ok(false)
+    }
+}
+
+/// Reorders the first `top_n` (20 by default) of `candidates`, the texts of
+/// the top search results, by how well the reranker says each one answers
+/// `query`. Scoring goes down the list and stops before it would overrun
+/// `budget_ms`; candidates it didn't get to follow the scored ones in their
+/// original order, as all of them do without a reranker.
+#[frb(sync)]
+pub fn rerank(query: String, candidates: Vec<String>, budget_ms: Option<i32>, top_n: Option<i32>) -> Result<Vec<RerankedCandidate>> {
+    let top_n = top_n.map_or(DEFAULT_TOP_N, |top_n| top_n.max(0) as usize).min(candidates.len());
+
+    #[cfg(feature = "onnx")]
+    let scores = {
+        let reranker = RERANKER.read().unwrap().clone();
+        match reranker {
+            Some(reranker) => {
+                let start = Instant::now();
+                let budget = budget_ms.map(|ms| Duration::from_millis(ms.max(0) as u64));
+                let scores = reranker.score(&query, &candidates[..top_n], budget)?;
+                println!("Reranked {}/{} candidates in {:?}", scores.len(), candidates.len(), start.elapsed());
+                scores
+            }
+            None => Vec::new(),
+        }
+    };
+    #[cfg(not(feature = "onnx"))]
+    let scores: Vec<f32> = {
+        let _ = (query, budget_ms, top_n);
+        Vec::new()
+    };
+
+    Ok(order_by_scores(candidates.len(), &scores))
+}
+
+/// All `candidates` positions, the ones with `scores` (a prefix of them) by
+/// score and the rest after them in their order
+fn order_by_scores(candidates: usize, scores: &[f32]) -> Vec<RerankedCandidate> {
+    let mut reranked: Vec<RerankedCandidate> = (0..candidates)
+        .map(|index| RerankedCandidate { index: index as i32, score: scores.get(index).map(|&score| score as f64) })
+        .collect();
+    // Stable, so equal scores keep their original order too
+    reranked[..scores.len()].sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    reranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indices(reranked: &[RerankedCandidate]) -> Vec<i32> {
+        reranked.iter().map(|candidate| candidate.index).collect()
+    }
+
+    #[test]
+    fn scored_candidates_go_first() {
+        let reranked = order_by_scores(4, &[0.1, 0.9, 0.5, 0.9]);
+        assert_eq!(indices(&reranked), vec![1, 3, 2, 0]);
+        assert_eq!(reranked[0].score, Some(0.9f32 as f64));
+    }
+
+    #[test]
+    fn unscored_candidates_keep_their_order() {
+        // The budget ran out after two, or only the top two were scored
+        let reranked = order_by_scores(5, &[0.2, 0.7]);
+        assert_eq!(indices(&reranked), vec![1, 0, 2, 3, 4]);
+        assert!(reranked[2..].iter().all(|candidate| candidate.score.is_none()));
+        assert_eq!(indices(&order_by_scores(3, &[])), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn missing_model_keeps_the_order() {
+        let dir = std::env::temp_dir().join(format!("reranker-missing-{}", std::process::id()));
+        assert!(!init_reranker(dir.to_string_lossy().into_owned()).unwrap());
+        let candidates = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let reranked = rerank("query".to_string(), candidates, Some(0), Some(2)).unwrap();
+        assert_eq!(indices(&reranked), vec![0, 1, 2]);
+        assert!(reranked.iter().all(|candidate| candidate.score.is_none()));
+    }
+}
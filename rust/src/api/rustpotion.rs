@@ -1,62 +1,253 @@
-use rustpotion::{RustPotion, PotionModel};
+use rustpotion::{IndexManifest, LongText, RustPotion, PotionError, TokenWeighting};
+use crate::api::embedding::EmbeddingRole;
+use crate::backends::{self, potion::PotionBackend, EmbeddingBackend};
 use flutter_rust_bridge::frb;
 use anyhow::Result;
 use std::path::PathBuf;
-use std::sync::OnceLock;
-use std::time::Instant;
+use std::sync::Arc;
 
 // Just mark it as opaque without deriving traits
 #[frb]
 pub struct PotionWrapper(pub(crate) RustPotion);
 
-static POTION: OnceLock<RustPotion> = OnceLock::new();
+/// Sets how many tokens of a text `model_id` embeds. With `sliding_window_stride`,
+/// long texts are embedded as windows of `max_tokens` tokens that start every
+/// `sliding_window_stride` tokens, and the windows are averaged, so nothing is
+/// cut off. A `max_tokens` of 0 embeds every token in one go. Other models keep
+/// their budget, and swapping the model resets it to 512 tokens.
+#[frb(sync)]
+pub fn set_embedding_token_budget(model_id: String, max_tokens: i32, sliding_window_stride: Option<i32>) -> Result<bool> {
+    let long_text = match (max_tokens, sliding_window_stride) {
+        (max_tokens, _) if max_tokens <= 0 => LongText::Full,
+        (max_tokens, Some(stride)) => LongText::SlidingWindow { window: max_tokens as usize, stride: stride.max(0) as usize },
+        (max_tokens, None) => LongText::Truncate { max_tokens: max_tokens as usize },
+    };
+    long_text.validate()?;
+    let backend = backends::get_backend(&model_id)?;
+    *potion_backend(&backend)?.long_text.write().unwrap() = long_text;
+    Ok(true)
+}
+
+fn potion_backend(backend: &Arc<dyn EmbeddingBackend>) -> Result<&PotionBackend> {
+    backend.potion()
+        .ok_or_else(|| anyhow::anyhow!("Embedding model '{}' is not a RustPotion model", backend.model_id()))
+}
+
+fn get_potion(model_id: &str) -> Result<Arc<RustPotion>> {
+    Ok(potion_backend(&backends::get_backend(model_id)?)?.potion.clone())
+}
+
+/// Loads a model2vec-format directory (model.safetensors, tokenizer.json and
+/// optionally config.json), e.g. `<app dir>/models/RETRIEVAL32M`, as `model_id`.
+#[frb(sync)]
+pub fn init_potion(model_id: String, model_path: String) -> Result<bool> {
+    let model_path = PathBuf::from(&model_path);
+    println!("Initializing RustPotion '{}' with model path: {:?}", model_id, model_path);
+    set_potion(&model_id, false, || RustPotion::from_dir(&model_path))
+}
+
+/// Like `init_potion`, but returns `dimensions`-long embeddings: projected onto
+/// the PCA components in `<model_path>/pca.safetensors` when that file exists,
+/// otherwise truncated (for Matryoshka-style models).
+#[frb(sync)]
+pub fn init_potion_reduced(model_id: String, model_path: String, dimensions: i32) -> Result<bool> {
+    let model_path = PathBuf::from(&model_path);
+    let pca_path = model_path.join("pca.safetensors");
+    println!("Initializing RustPotion '{}' with model path: {:?}, {} dimensions", model_id, model_path, dimensions);
+    set_potion(&model_id, false, || {
+        let potion = RustPotion::from_dir(&model_path)?;
+        if pca_path.exists() {
+            potion.with_pca(&pca_path, Some(dimensions.max(0) as usize))
+        } else {
+            potion.with_truncation(dimensions.max(0) as usize)
+        }
+    })
+}
+
+/// Like `init_potion`, pooling with the token weights saved by `compute_token_weights`
+#[frb(sync)]
+pub fn init_potion_weighted(model_id: String, model_path: String, weights_file: String) -> Result<bool> {
+    let model_path = PathBuf::from(&model_path);
+    println!("Initializing RustPotion '{}' with model path: {:?}, weights: {}", model_id, model_path, weights_file);
+    set_potion(&model_id, false, || {
+        let weights = rustpotion::load_token_weights(&PathBuf::from(&weights_file))?;
+        RustPotion::from_dir(&model_path)?.with_token_weights(Some(weights))
+    })
+}
+
+/// Like `init_potion`, for model files that don't use the model2vec file names
+#[frb(sync)]
+pub fn init_potion_from_files(model_id: String, model_file: String, tokenizer_file: String, config_file: Option<String>) -> Result<bool> {
+    println!("Initializing RustPotion '{}' from {} and {}", model_id, model_file, tokenizer_file);
+    set_potion(&model_id, false, || RustPotion::from_files(
+        &PathBuf::from(model_file),
+        &PathBuf::from(tokenizer_file),
+        config_file.as_ref().map(PathBuf::from).as_deref(),
+    ))
+}
+
+/// Like `init_potion`, from model files already loaded in memory, e.g. Flutter
+/// assets read with `rootBundle.load`, so they don't have to be copied first.
+#[frb(sync)]
+pub fn init_potion_from_bytes(model_id: String, model_bytes: Vec<u8>, tokenizer_bytes: Vec<u8>, config_bytes: Option<Vec<u8>>) -> Result<bool> {
+    println!("Initializing RustPotion '{}' from {} model bytes", model_id, model_bytes.len());
+    set_potion(&model_id, false, || RustPotion::from_bytes(model_bytes, &tokenizer_bytes, config_bytes.as_deref()))
+}
+
+/// Loads `model_path` and puts it in place of whatever `model_id` was, e.g.
+/// after a model upgrade. The old model keeps serving calls already running.
+#[frb(sync)]
+pub fn swap_potion(model_id: String, model_path: String) -> Result<bool> {
+    let model_path = PathBuf::from(&model_path);
+    println!("Swapping RustPotion '{}' to model path: {:?}", model_id, model_path);
+    set_potion(&model_id, true, || RustPotion::from_dir(&model_path))
+}
+
+/// Drops `model_id`, false if it wasn't loaded
+#[frb(sync)]
+pub fn unload_potion(model_id: String) -> bool {
+    backends::unload_backend(&model_id)
+}
+
+/// Ids of the models that are currently loaded
+#[frb(sync)]
+pub fn loaded_potion_models() -> Vec<String> {
+    backends::loaded_backends().iter()
+        .filter(|backend| backend.potion().is_some())
+        .map(|backend| backend.model_id().to_string())
+        .collect()
+}
+
+/// Length of the embeddings `model_id` returns
+#[frb(sync)]
+pub fn potion_dimensions(model_id: String) -> Result<i32> {
+    Ok(get_potion(&model_id)?.dimensions() as i32)
+}
 
+/// Saves which model (manifest.json of its directory) and vector size built
+/// the index at `index_manifest_file`, for `check_index_model`.
 #[frb(sync)]
-pub fn init_potion(app_dir: String) -> Result<bool> {
-    let start = Instant::now();
-    let model_path = PathBuf::from(&app_dir).join("models");
-    
-    // Ensure model directory exists
-    if !model_path.exists() {
-        std::fs::create_dir_all(&model_path)
-            .expect("Failed to create model directory");
+pub fn record_index_model(model_id: String, index_manifest_file: String) -> Result<bool> {
+    let potion = get_potion(&model_id)?;
+    let index_manifest = potion.index_manifest()
+        .ok_or_else(|| anyhow::anyhow!("RustPotion model '{}' has no manifest.json", model_id))?;
+    index_manifest.save(&PathBuf::from(index_manifest_file))?;
+    Ok(true)
+}
+
+/// True if `model_id` built the index recorded in `index_manifest_file`, false
+/// if nothing was recorded yet, and an error saying what differs otherwise.
+#[frb(sync)]
+pub fn check_index_model(model_id: String, index_manifest_file: String) -> Result<bool> {
+    let index_manifest_file = PathBuf::from(index_manifest_file);
+    if !index_manifest_file.exists() {
+        return Ok(false);
     }
-    
-    println!("Initializing RustPotion with model path: {:?}", model_path);
-    
-    POTION.get_or_init(|| {
-        RustPotion::new(PotionModel::RETRIEVAL32M, &model_path)
-    });
-    
-    println!("RustPotion initialization took: {:?}", start.elapsed());
+    let potion = get_potion(&model_id)?;
+    potion.check_index(&IndexManifest::load(&index_manifest_file)?)?;
     Ok(true)
 }
 
+fn set_potion(model_id: &str, replace: bool, load: impl FnOnce() -> Result<RustPotion, PotionError>) -> Result<bool> {
+    backends::set_backend(model_id, replace, || {
+        let potion = load()
+            .map_err(|e| anyhow::anyhow!("Failed to load RustPotion model '{}': {}", model_id, e))?;
+        Ok(Arc::new(PotionBackend::new(model_id, potion)))
+    })
+}
+
 #[frb(sync)]
-pub fn get_embedding_from_rustpotion(text: String) -> Result<Vec<f64>> {
-    let potion = POTION.get()
-        .ok_or_else(|| anyhow::anyhow!("RustPotion not initialized"))?;
-    
-    // Truncate text to 400 chars
-    let truncated = text.chars().take(2500).collect::<String>();
-    Ok(potion.encode(&truncated)
+pub fn get_embedding_from_rustpotion(model_id: String, text: String, role: EmbeddingRole) -> Result<Vec<f64>> {
+    let backend = backends::get_backend(&model_id)?;
+
+    Ok(backends::cache::embed_cached(potion_backend(&backend)?, text, role)?
         .into_iter()
         .map(|x| x as f64)
         .collect())
 }
 
 #[frb(sync)]
-pub fn get_embeddings_from_rustpotion(texts: Vec<String>) -> Result<Vec<Vec<f64>>> {
-    let potion = POTION.get()
-        .ok_or_else(|| anyhow::anyhow!("RustPotion not initialized"))?;
-    
-    // Truncate each text to 400 chars before encoding
-    let truncated_texts: Vec<String> = texts.into_iter()
-        .map(|text| text.chars().take(2500).collect())
-        .collect();
-    
-    Ok(potion.encode_many(truncated_texts)
+pub fn get_embeddings_from_rustpotion(model_id: String, texts: Vec<String>, role: EmbeddingRole) -> Result<Vec<Vec<f64>>> {
+    let backend = backends::get_backend(&model_id)?;
+
+    Ok(backends::cache::embed_batch_cached(potion_backend(&backend)?, texts, role)?
         .into_iter()
         .map(|vec| vec.into_iter().map(|x| x as f64).collect())
         .collect())
-} 
\ No newline at end of file
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchWord {
+    pub word: String,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchExplanation {
+    pub similarity: f64,
+    // Highest contributions first
+    pub query_words: Vec<MatchWord>,
+    pub text_words: Vec<MatchWord>,
+}
+
+/// Why `text` matched `query`: the `top_k` words on each side that add the most
+/// to the cosine similarity, so a result snippet can highlight them even when
+/// they don't appear in the query.
+#[frb(sync)]
+pub fn explain_match(model_id: String, query: String, text: String, top_k: i32) -> Result<MatchExplanation> {
+    let potion = get_potion(&model_id)?;
+
+    let explanation = potion.explain_match(&query, &text, top_k.max(0) as usize)?;
+    let words = |words: Vec<rustpotion::WordContribution>| words.into_iter()
+        .map(|word| MatchWord { word: word.word, score: word.score as f64 })
+        .collect();
+    Ok(MatchExplanation {
+        similarity: explanation.similarity as f64,
+        query_words: words(explanation.query_words),
+        text_words: words(explanation.text_words),
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct SimilarWord {
+    pub word: String,
+    pub similarity: f64,
+}
+
+/// Collects the words of the indexed section texts that `nearest_corpus_words`
+/// can suggest for `model_id`, replacing any earlier set. Returns how many.
+pub fn build_corpus_words(model_id: String, texts: Vec<String>) -> Result<i32> {
+    let backend = backends::get_backend(&model_id)?;
+    let potion = potion_backend(&backend)?;
+
+    let words = potion.potion.corpus_words(texts.iter().map(|text| text.as_str()))?;
+    let count = words.len() as i32;
+    *potion.corpus_words.write().unwrap() = Some(Arc::new(words));
+    Ok(count)
+}
+
+/// The `k` corpus words closest in meaning to `term` (e.g. "bleeding" ->
+/// "hemorrhage"), for suggesting or adding synonyms to an exact-phrase search.
+#[frb(sync)]
+pub fn nearest_corpus_words(model_id: String, term: String, k: i32) -> Result<Vec<SimilarWord>> {
+    let backend = backends::get_backend(&model_id)?;
+    let potion = potion_backend(&backend)?;
+    let words = potion.corpus_words.read().unwrap()
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("No corpus words built for RustPotion model '{}'", model_id))?;
+
+    Ok(potion.potion.nearest_words(&term, &words, k.max(0) as usize)?
+        .into_iter()
+        .map(|(word, similarity)| SimilarWord { word, similarity: similarity as f64 })
+        .collect())
+}
+
+/// Computes IDF token weights from the indexed section texts with `model_id`
+/// and saves them to `weights_file`, for `init_potion_weighted`.
+pub fn compute_token_weights(model_id: String, texts: Vec<String>, weights_file: String) -> Result<bool> {
+    let potion = get_potion(&model_id)?;
+
+    let weights = potion.compute_token_weights(texts.iter().map(|text| text.as_str()), TokenWeighting::Idf)?;
+    rustpotion::save_token_weights(&weights, &PathBuf::from(weights_file))?;
+    Ok(true)
+}
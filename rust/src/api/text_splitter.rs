@@ -1,6 +1,6 @@
 use text_splitter::TextSplitter;
 use anyhow::Result;
-use crate::api::rustpotion;
+use crate::api::embedding::{self, EmbeddingRole};
 use flutter_rust_bridge::frb;
 
 #[flutter_rust_bridge::frb(sync)]
@@ -42,7 +42,7 @@ pub fn split_text(text: String, max_chars: i32) -> Result<Vec<String>> {
 }
 
 #[frb(sync)]
-pub fn semantic_chunking(text: String, max_chars: i32) -> Result<Vec<String>> {
+pub fn semantic_chunking(model_id: String, text: String, max_chars: i32) -> Result<Vec<String>> {
     // First do a rough split to get manageable chunks
     let initial_chunks = split_text(text, max_chars)?;
     let mut final_chunks = Vec::new();
@@ -53,7 +53,7 @@ pub fn semantic_chunking(text: String, max_chars: i32) -> Result<Vec<String>> {
     
     for chunk in initial_chunks {
         // Get embedding for current chunk
-        let chunk_embedding = rustpotion::get_embedding_from_rustpotion(chunk.clone())?;
+        let chunk_embedding = embedding::get_embedding(model_id.clone(), chunk.clone(), EmbeddingRole::Document)?;
         
         // If we have a previous chunk to compare to
         if let Some(last_emb) = &last_embedding {
@@ -91,7 +91,7 @@ pub fn semantic_chunking(text: String, max_chars: i32) -> Result<Vec<String>> {
 }
 
 #[frb(sync)]
-pub fn semantic_chunking_v2(text: String, min_chunk_size: i32) -> Result<Vec<String>> {
+pub fn semantic_chunking_v2(model_id: String, text: String, min_chunk_size: i32) -> Result<Vec<String>> {
     println!("\n=== Starting Semantic Chunking V2 ===");
     println!("Original text length: {} chars", text.len());
     println!("First 100 chars: {}", text.chars().take(100).collect::<String>());
@@ -121,7 +121,7 @@ pub fn semantic_chunking_v2(text: String, min_chunk_size: i32) -> Result<Vec<Str
 
     // Get embeddings
     println!("\nGetting embeddings for sentences...");
-    let sentence_embeddings = rustpotion::get_embeddings_from_rustpotion(sentences.clone())?;
+    let sentence_embeddings = embedding::get_embeddings(model_id, sentences.clone(), EmbeddingRole::Document)?;
     println!("Got {} embeddings", sentence_embeddings.len());
     
     // Calculate distances
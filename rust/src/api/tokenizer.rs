@@ -31,18 +31,24 @@ fn get_or_init_tokenizer(app_dir: &str) -> Result<&'static Tokenizer> {
 pub fn init_tokenizer(tokenizer_path: String) -> Result<bool> {
     eprintln!("Loading tokenizer from: {:?}", tokenizer_path);
     
-    TOKENIZER.get_or_init(|| {
-        Tokenizer::from_file(&tokenizer_path)
-            .expect("Failed to load tokenizer")
-    });
+    if TOKENIZER.get().is_none() {
+        let tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {}", e))?;
+        // Keeps a tokenizer another call set meanwhile, as get_or_init did
+        let _ = TOKENIZER.set(tokenizer);
+    }
     
     Ok(true)
 }
 
+pub(crate) fn get_tokenizer() -> Result<&'static Tokenizer> {
+    TOKENIZER.get()
+        .ok_or_else(|| anyhow::anyhow!("Tokenizer not initialized"))
+}
+
 #[frb(sync)]
 pub fn encode_text(input: String) -> Result<TokenizerOutput> {
-    let tokenizer = TOKENIZER.get()
-        .ok_or_else(|| anyhow::anyhow!("Tokenizer not initialized"))?;
+    let tokenizer = get_tokenizer()?;
     
     let encoding = tokenizer.encode(input, true)
         .map_err(|e| anyhow::anyhow!("{}", e))?;
@@ -0,0 +1,632 @@
+/*
+    Embeddings kept on disk between runs, so re-importing a database or
+    re-indexing after a splitter change only embeds the chunks that changed.
+    One file of little-endian f32s per embedding, in a directory per model:
+    <cache dir>/<model id hash>/<text hash>. The text hash covers the model's
+    fingerprint too, so a model replaced under the same id misses. The least
+    recently used files go once the cache outgrows its size limit; the order
+    they were used in is kept in memory and written to <cache dir>/recency.txt
+    now and then, not on every hit. The state lock only covers that
+    bookkeeping; files are read, written and removed outside it.
+*/
+use super::EmbeddingBackend;
+use crate::api::embedding::EmbeddingRole;
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+
+// Evicting down to this share of the limit, so not every insert past it
+// has to sort the entries again
+const EVICT_TO: f64 = 0.9;
+
+// Paths of the cached embeddings, least recently used first
+const RECENCY_FILE: &str = "recency.txt";
+
+// Least time between writes of the recency file
+const FLUSH_EVERY: Duration = Duration::from_secs(30);
+
+// The open cache, if any. Without one embeddings are always computed.
+static CACHE: RwLock<Option<Arc<EmbeddingCache>>> = RwLock::new(None);
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: u64,
+    pub bytes: u64,
+}
+
+struct Entry {
+    bytes: u64,
+    // Value of `CacheState::clock` when it was last read or written
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheState {
+    // By path relative to the cache directory, "<model id hash>/<text hash>"
+    entries: HashMap<String, Entry>,
+    bytes: u64,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+    // Bumped whenever embeddings are dropped on purpose, so a batch embedded
+    // across it doesn't write them back
+    generation: u64,
+    // Whether the order of use changed since the recency file was written
+    dirty: bool,
+    flushed: Option<Instant>,
+}
+
+pub struct EmbeddingCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    state: Mutex<CacheState>,
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Directory of a model's embeddings, so any model id makes a valid file name
+fn model_dir(model_id: &str) -> String {
+    hex(&Sha256::digest(model_id.as_bytes())[..8])
+}
+
+// A name beside `path` to write it under first, apart from any other thread
+// writing the same file
+fn tmp_path(path: &Path) -> PathBuf {
+    static WRITES: AtomicU64 = AtomicU64::new(0);
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}.tmp", WRITES.fetch_add(1, Ordering::Relaxed)));
+    path.with_file_name(name)
+}
+
+/// Key of `text` embedded as `role` with `settings`. Runs of whitespace count
+/// as one space and ends are trimmed, as tokenizers split on whitespace anyway.
+fn text_key(text: &str, role: EmbeddingRole, settings: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{:?}\0{}\0", role, settings));
+    for (i, word) in text.split_whitespace().enumerate() {
+        if i > 0 {
+            hasher.update(b" ");
+        }
+        hasher.update(word.as_bytes());
+    }
+    hex(&hasher.finalize())
+}
+
+impl EmbeddingCache {
+    /// Opens the cache in `dir`, creating it if needed. Files it already holds
+    /// are used in the order of the recency file, then those written after it
+    /// in the order they were modified.
+    pub fn open(dir: &Path, max_bytes: u64) -> Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let recency: HashMap<String, usize> = std::fs::read_to_string(dir.join(RECENCY_FILE))
+            .unwrap_or_default()
+            .lines()
+            .enumerate()
+            .map(|(i, path)| (path.to_string(), i))
+            .collect();
+        let mut found = Vec::new();
+        for model in std::fs::read_dir(dir)? {
+            let model = model?;
+            if !model.file_type()?.is_dir() {
+                continue;
+            }
+            for file in std::fs::read_dir(model.path())? {
+                let file = file?;
+                let name = file.file_name().to_string_lossy().into_owned();
+                // Left over from a write that didn't finish
+                if name.ends_with(".tmp") {
+                    let _ = std::fs::remove_file(file.path());
+                    continue;
+                }
+                let metadata = file.metadata()?;
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                let path = format!("{}/{}", model.file_name().to_string_lossy(), name);
+                let rank = recency.get(&path).copied().unwrap_or(usize::MAX);
+                found.push((rank, modified, path, metadata.len()));
+            }
+        }
+        found.sort();
+
+        let mut state = CacheState::default();
+        for (_, _, path, bytes) in found {
+            state.clock += 1;
+            state.bytes += bytes;
+            state.entries.insert(path, Entry { bytes, last_used: state.clock });
+        }
+        let cache = EmbeddingCache { dir: dir.to_path_buf(), max_bytes, state: Mutex::new(state) };
+        let evicted = cache.evict(&mut cache.state.lock().unwrap());
+        cache.remove_files(&evicted);
+        Ok(cache)
+    }
+
+    /// The embedding cached at `path`, if it is there and `dimensions` long.
+    /// Reads the file only, without the state lock.
+    fn read(&self, path: &str, dimensions: usize) -> Option<Vec<f32>> {
+        std::fs::read(self.dir.join(path)).ok()
+            .filter(|bytes| bytes.len() == dimensions * 4)
+            .map(|bytes| bytes.chunks_exact(4).map(|b| f32::from_le_bytes(b.try_into().unwrap())).collect())
+    }
+
+    /// Writes `embedding` to `path` and returns its size, without the state lock
+    fn write(&self, path: &str, embedding: &[f32]) -> Result<u64> {
+        let bytes: Vec<u8> = embedding.iter().flat_map(|x| x.to_le_bytes()).collect();
+        let file_path = self.dir.join(path);
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Written aside and renamed, so a crash never leaves half an embedding
+        let tmp_path = tmp_path(&file_path);
+        std::fs::write(&tmp_path, &bytes)?;
+        std::fs::rename(&tmp_path, &file_path)?;
+        Ok(bytes.len() as u64)
+    }
+
+    fn touch(&self, state: &mut CacheState, path: &str) {
+        state.clock += 1;
+        let clock = state.clock;
+        if let Some(entry) = state.entries.get_mut(path) {
+            entry.last_used = clock;
+            state.dirty = true;
+        }
+    }
+
+    fn insert(&self, state: &mut CacheState, path: &str, bytes: u64) {
+        self.remove_entry(state, path);
+        state.clock += 1;
+        state.dirty = true;
+        state.bytes += bytes;
+        state.entries.insert(path.to_string(), Entry { bytes, last_used: state.clock });
+    }
+
+    fn remove_entry(&self, state: &mut CacheState, path: &str) {
+        if let Some(entry) = state.entries.remove(path) {
+            state.bytes -= entry.bytes;
+            state.dirty = true;
+        }
+    }
+
+    fn remove_files(&self, paths: &[String]) {
+        for path in paths {
+            let _ = std::fs::remove_file(self.dir.join(path));
+        }
+    }
+
+    /// Drops the least recently used embeddings while over the size limit and
+    /// returns their paths, whose files the caller removes once it let go of
+    /// the lock
+    fn evict(&self, state: &mut CacheState) -> Vec<String> {
+        if state.bytes <= self.max_bytes {
+            return Vec::new();
+        }
+        let target = (self.max_bytes as f64 * EVICT_TO) as u64;
+        let mut by_use: Vec<(u64, String)> = state.entries.iter()
+            .map(|(path, entry)| (entry.last_used, path.clone()))
+            .collect();
+        by_use.sort_unstable();
+        let mut evicted = Vec::new();
+        for (_, path) in by_use {
+            if state.bytes <= target {
+                break;
+            }
+            self.remove_entry(state, &path);
+            evicted.push(path);
+        }
+        evicted
+    }
+
+    /// Writes the order of use to the recency file if it changed. Only taking
+    /// the order is done under the lock.
+    fn flush(&self) -> Result<()> {
+        let recency = {
+            let mut state = self.state.lock().unwrap();
+            if !state.dirty {
+                return Ok(());
+            }
+            let mut by_use: Vec<(u64, &str)> = state.entries.iter()
+                .map(|(path, entry)| (entry.last_used, path.as_str()))
+                .collect();
+            by_use.sort_unstable();
+            let mut recency = String::new();
+            for (_, path) in by_use {
+                recency.push_str(path);
+                recency.push('\n');
+            }
+            state.dirty = false;
+            state.flushed = Some(Instant::now());
+            recency
+        };
+        let recency_path = self.dir.join(RECENCY_FILE);
+        let tmp_path = tmp_path(&recency_path);
+        let written = std::fs::write(&tmp_path, recency)
+            .and_then(|()| std::fs::rename(&tmp_path, &recency_path));
+        if written.is_err() {
+            // Tried again at the next flush
+            self.state.lock().unwrap().dirty = true;
+        }
+        Ok(written?)
+    }
+
+    /// Embeddings of `texts` as `role`, reading the ones computed before and
+    /// embedding the rest in one batch with `backend`
+    pub fn embed_batch(&self, backend: &dyn EmbeddingBackend, texts: Vec<String>, role: EmbeddingRole) -> Result<Vec<Vec<f32>>> {
+        let model = model_dir(backend.model_id());
+        let settings = format!("{}\0{}", backend.fingerprint()?, backend.settings());
+        let paths: Vec<String> = texts.iter()
+            .map(|text| format!("{}/{}", model, text_key(text, role, &settings)))
+            .collect();
+        // Where each distinct text's embedding goes
+        let mut unique: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut unique_order = Vec::new();
+        for (i, path) in paths.iter().enumerate() {
+            unique.entry(path.as_str()).or_insert_with(|| {
+                unique_order.push(i);
+                Vec::new()
+            }).push(i);
+        }
+
+        // Which are cached is looked up under the lock, their files are read
+        // after it. One dropped in between reads as a miss.
+        let (cached, generation) = {
+            let state = self.state.lock().unwrap();
+            let cached: Vec<bool> = unique_order.iter()
+                .map(|&i| state.entries.contains_key(&paths[i]))
+                .collect();
+            (cached, state.generation)
+        };
+        let read: Vec<Option<Vec<f32>>> = unique_order.iter().zip(&cached)
+            .map(|(&i, &cached)| if cached { self.read(&paths[i], backend.dimensions()) } else { None })
+            .collect();
+
+        let mut embeddings = vec![Vec::new(); texts.len()];
+        let mut missing_texts = Vec::new();
+        {
+            let mut state = self.state.lock().unwrap();
+            for ((&i, was_cached), embedding) in unique_order.iter().zip(cached).zip(read) {
+                let path = paths[i].as_str();
+                match embedding {
+                    Some(embedding) => {
+                        state.hits += unique[path].len() as u64;
+                        self.touch(&mut state, path);
+                        for &j in &unique[path] {
+                            embeddings[j] = embedding.clone();
+                        }
+                    }
+                    None => {
+                        state.misses += 1;
+                        // Gone, cut short, or from a model of another width
+                        // under the same id. Writing it below replaces the file.
+                        if was_cached {
+                            self.remove_entry(&mut state, path);
+                        }
+                        missing_texts.push(i);
+                    }
+                }
+            }
+        }
+        if missing_texts.is_empty() {
+            self.flush_now_and_then();
+            return Ok(embeddings);
+        }
+
+        // Embed without holding the lock, other batches may hit meanwhile
+        let computed = backend.embed_batch(missing_texts.iter().map(|&i| texts[i].clone()).collect(), role)?;
+        let mut written = Vec::new();
+        for (&i, embedding) in missing_texts.iter().zip(&computed) {
+            let path = paths[i].as_str();
+            match self.write(path, embedding) {
+                Ok(bytes) => written.push((path, bytes)),
+                Err(e) => println!("Failed to cache embedding {}: {}", path, e),
+            }
+        }
+
+        // The model's embeddings were dropped meanwhile, e.g. as it was
+        // replaced, so these may be the old model's and aren't kept. A batch
+        // that looked up after the drop with the old backend still writes, but
+        // under the old fingerprint, which the new model never reads.
+        let (kept, evicted) = {
+            let mut state = self.state.lock().unwrap();
+            let kept = state.generation == generation;
+            if kept {
+                for &(path, bytes) in &written {
+                    self.insert(&mut state, path, bytes);
+                }
+            }
+            (kept, self.evict(&mut state))
+        };
+        if !kept {
+            self.remove_files(&written.iter().map(|&(path, _)| path.to_string()).collect::<Vec<_>>());
+        }
+        self.remove_files(&evicted);
+
+        for (&i, embedding) in missing_texts.iter().zip(computed) {
+            for &j in &unique[paths[i].as_str()] {
+                embeddings[j] = embedding.clone();
+            }
+        }
+        self.flush_now_and_then();
+        Ok(embeddings)
+    }
+
+    fn flush_now_and_then(&self) {
+        let flushed = self.state.lock().unwrap().flushed;
+        if flushed.is_some_and(|flushed| flushed.elapsed() < FLUSH_EVERY) {
+            return;
+        }
+        if let Err(e) = self.flush() {
+            println!("Failed to write the embedding cache order: {}", e);
+        }
+    }
+
+    /// Drops every embedding of `model_id`. The files go after the lock is
+    /// let go of; an embedding written meanwhile that goes with them is a miss.
+    pub fn forget_model(&self, model_id: &str) -> Result<()> {
+        let model = model_dir(model_id);
+        {
+            let mut state = self.state.lock().unwrap();
+            state.generation += 1;
+            let paths: Vec<String> = state.entries.keys()
+                .filter(|path| path.split('/').next() == Some(model.as_str()))
+                .cloned()
+                .collect();
+            for path in paths {
+                self.remove_entry(&mut state, &path);
+            }
+        }
+        let model_path = self.dir.join(&model);
+        if model_path.exists() {
+            std::fs::remove_dir_all(model_path)?;
+        }
+        Ok(())
+    }
+
+    /// Drops every embedding, keeping the hit and miss counts
+    pub fn clear(&self) -> Result<()> {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.generation += 1;
+            state.dirty = true;
+            state.entries.clear();
+            state.bytes = 0;
+        }
+        for model in std::fs::read_dir(&self.dir)? {
+            let model = model?;
+            if model.file_type()?.is_dir() {
+                std::fs::remove_dir_all(model.path())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let state = self.state.lock().unwrap();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            entries: state.entries.len() as u64,
+            bytes: state.bytes,
+        }
+    }
+}
+
+impl Drop for EmbeddingCache {
+    fn drop(&mut self) {
+        if self.state.is_poisoned() {
+            return;
+        }
+        if let Err(e) = self.flush() {
+            println!("Failed to write the embedding cache order: {}", e);
+        }
+    }
+}
+
+/// Opens the cache in `dir` in place of any open one, or closes it with `None`
+pub fn set_cache(cache: Option<(&Path, u64)>) -> Result<()> {
+    let cache = match cache {
+        Some((dir, max_bytes)) => Some(Arc::new(EmbeddingCache::open(dir, max_bytes)?)),
+        None => None,
+    };
+    *CACHE.write().unwrap() = cache;
+    Ok(())
+}
+
+pub fn cache() -> Option<Arc<EmbeddingCache>> {
+    CACHE.read().unwrap().clone()
+}
+
+/// `backend.embed_batch`, through the cache when one is open
+pub fn embed_batch_cached(backend: &dyn EmbeddingBackend, texts: Vec<String>, role: EmbeddingRole) -> Result<Vec<Vec<f32>>> {
+    match cache() {
+        Some(cache) => cache.embed_batch(backend, texts, role),
+        None => backend.embed_batch(texts, role),
+    }
+}
+
+/// `backend.embed`, through the cache when one is open
+pub fn embed_cached(backend: &dyn EmbeddingBackend, text: String, role: EmbeddingRole) -> Result<Vec<f32>> {
+    match cache() {
+        Some(cache) => Ok(cache.embed_batch(backend, vec![text], role)?.remove(0)),
+        None => backend.embed(&text, role),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // Two values per text, its length and its role, counting what it embeds
+    struct FakeBackend {
+        fingerprint: &'static str,
+        embedded: AtomicUsize,
+        // Dropped from while embedding, as when the model is replaced mid-batch
+        forget_from: Option<Arc<EmbeddingCache>>,
+    }
+
+    impl FakeBackend {
+        fn new(fingerprint: &'static str) -> Self {
+            FakeBackend { fingerprint, embedded: AtomicUsize::new(0), forget_from: None }
+        }
+
+        fn embedded(&self) -> usize {
+            self.embedded.load(Ordering::SeqCst)
+        }
+    }
+
+    impl EmbeddingBackend for FakeBackend {
+        fn model_id(&self) -> &str {
+            "fake"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        fn embed(&self, text: &str, role: EmbeddingRole) -> Result<Vec<f32>> {
+            Ok(self.embed_batch(vec![text.to_string()], role)?.remove(0))
+        }
+
+        fn embed_batch(&self, texts: Vec<String>, role: EmbeddingRole) -> Result<Vec<Vec<f32>>> {
+            if let Some(cache) = &self.forget_from {
+                cache.forget_model(self.model_id())?;
+            }
+            self.embedded.fetch_add(texts.len(), Ordering::SeqCst);
+            Ok(texts.iter().map(|text| vec![text.len() as f32, (role == EmbeddingRole::Query) as u8 as f32]).collect())
+        }
+
+        fn fingerprint(&self) -> Result<String> {
+            Ok(self.fingerprint.to_string())
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("embedding-cache-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn texts(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|text| text.to_string()).collect()
+    }
+
+    #[test]
+    fn whitespace_doesnt_change_the_key() {
+        let dir = temp_dir("whitespace");
+        let cache = EmbeddingCache::open(&dir, 1 << 20).unwrap();
+        let backend = FakeBackend::new("v1");
+
+        let embeddings = cache.embed_batch(&backend, texts(&["a  b", " a b\n", "c"]), EmbeddingRole::Document).unwrap();
+        assert_eq!(backend.embedded(), 2);
+        assert_eq!(embeddings[0], embeddings[1]);
+        cache.embed_batch(&backend, texts(&["a b", "c"]), EmbeddingRole::Document).unwrap();
+        assert_eq!(backend.embedded(), 2);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries, stats.bytes), (2, 2, 2, 16));
+
+        // Queries and documents are embedded apart
+        let query = cache.embed_batch(&backend, texts(&["c"]), EmbeddingRole::Query).unwrap();
+        assert_eq!(backend.embedded(), 3);
+        assert_eq!(query[0], vec![1.0, 1.0]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn replaced_model_misses() {
+        let dir = temp_dir("replaced");
+        let cache = EmbeddingCache::open(&dir, 1 << 20).unwrap();
+        cache.embed_batch(&FakeBackend::new("v1"), texts(&["a"]), EmbeddingRole::Document).unwrap();
+        let replaced = FakeBackend::new("v2");
+        cache.embed_batch(&replaced, texts(&["a"]), EmbeddingRole::Document).unwrap();
+        assert_eq!(replaced.embedded(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn deleted_file_is_embedded_again() {
+        let dir = temp_dir("deleted");
+        let cache = EmbeddingCache::open(&dir, 1 << 20).unwrap();
+        let backend = FakeBackend::new("v1");
+        cache.embed_batch(&backend, texts(&["a", "b"]), EmbeddingRole::Document).unwrap();
+        let path = cache.state.lock().unwrap().entries.keys().next().unwrap().clone();
+        std::fs::remove_file(dir.join(&path)).unwrap();
+
+        cache.embed_batch(&backend, texts(&["a", "b"]), EmbeddingRole::Document).unwrap();
+        assert_eq!(backend.embedded(), 3);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries, stats.bytes), (1, 3, 2, 16));
+        assert!(dir.join(&path).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn least_recently_used_go_first() {
+        let dir = temp_dir("lru");
+        // Room for ten embeddings, evicting down to nine
+        let cache = EmbeddingCache::open(&dir, 80).unwrap();
+        let backend = FakeBackend::new("v1");
+        for i in 0..10 {
+            cache.embed_batch(&backend, vec![format!("text {}", i)], EmbeddingRole::Document).unwrap();
+        }
+        cache.embed_batch(&backend, texts(&["text 0"]), EmbeddingRole::Document).unwrap();
+        cache.embed_batch(&backend, texts(&["text 10"]), EmbeddingRole::Document).unwrap();
+        assert_eq!(backend.embedded(), 11);
+        assert_eq!(cache.stats().entries, 9);
+
+        // 0 was read since, so 1 and 2 went
+        cache.embed_batch(&backend, texts(&["text 0", "text 3"]), EmbeddingRole::Document).unwrap();
+        assert_eq!(backend.embedded(), 11);
+        cache.embed_batch(&backend, texts(&["text 1"]), EmbeddingRole::Document).unwrap();
+        assert_eq!(backend.embedded(), 12);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn order_of_use_survives_reopening() {
+        let dir = temp_dir("reopen");
+        let backend = FakeBackend::new("v1");
+        {
+            let cache = EmbeddingCache::open(&dir, 1 << 20).unwrap();
+            for text in ["a", "b", "c"] {
+                cache.embed_batch(&backend, texts(&[text]), EmbeddingRole::Document).unwrap();
+            }
+            cache.embed_batch(&backend, texts(&["a"]), EmbeddingRole::Document).unwrap();
+        }
+
+        // Room for two, so the least recently used one goes
+        let cache = EmbeddingCache::open(&dir, 20).unwrap();
+        assert_eq!(cache.stats().entries, 2);
+        cache.embed_batch(&backend, texts(&["a", "c"]), EmbeddingRole::Document).unwrap();
+        assert_eq!(backend.embedded(), 3);
+        cache.embed_batch(&backend, texts(&["b"]), EmbeddingRole::Document).unwrap();
+        assert_eq!(backend.embedded(), 4);
+        drop(cache);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn forgotten_model_isnt_written_back() {
+        let dir = temp_dir("forget");
+        let cache = Arc::new(EmbeddingCache::open(&dir, 1 << 20).unwrap());
+        let backend = FakeBackend::new("v1");
+        cache.embed_batch(&backend, texts(&["a", "b"]), EmbeddingRole::Document).unwrap();
+        cache.forget_model("fake").unwrap();
+        assert_eq!(cache.stats().entries, 0);
+        assert!(!dir.join(model_dir("fake")).exists());
+
+        // Forgotten again while this batch embeds, so nothing is kept
+        let swapped = FakeBackend { forget_from: Some(cache.clone()), ..FakeBackend::new("v1") };
+        let embeddings = cache.embed_batch(&swapped, texts(&["a"]), EmbeddingRole::Document).unwrap();
+        assert_eq!(embeddings, vec![vec![1.0, 0.0]]);
+        assert_eq!(cache.stats().entries, 0);
+        drop(swapped);
+        drop(cache);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
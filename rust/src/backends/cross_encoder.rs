@@ -0,0 +1,89 @@
+use super::onnx::{load_session, prepare_tokenizer, run_padded};
+use anyhow::Result;
+use ort::session::Session;
+use std::borrow::Cow;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokenizers::Tokenizer;
+
+// Pairs per inference run. Small, so a latency budget cuts scoring off close
+// to where it runs out.
+const BATCH_SIZE: usize = 8;
+
+/// A cross-encoder exported to ONNX, e.g. ms-marco-MiniLM-L-6-v2, that scores
+/// how well a text answers a query by reading both together
+pub struct CrossEncoder {
+    session: Session,
+    tokenizer: Cow<'static, Tokenizer>,
+    pad_id: u32,
+}
+
+impl CrossEncoder {
+    /// Loads model.onnx and tokenizer.json from `model_dir`
+    pub fn from_dir(model_dir: &Path) -> Result<Self> {
+        let mut tokenizer = Cow::Owned(Tokenizer::from_file(model_dir.join("tokenizer.json"))
+            .map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {}", e))?);
+        let pad_id = prepare_tokenizer(&mut tokenizer)?;
+        let session = load_session(&model_dir.join("model.onnx"))?;
+        Ok(CrossEncoder { session, tokenizer, pad_id })
+    }
+
+    /// Relevance of each of `texts` to `query`, higher is better, in the order
+    /// of `texts`. With a `budget`, stops before the batch that would likely
+    /// overrun it, so only the first texts may get a score.
+    pub fn score(&self, query: &str, texts: &[String], budget: Option<Duration>) -> Result<Vec<f32>> {
+        let start = Instant::now();
+        let mut scores = Vec::with_capacity(texts.len());
+
+        for (batch_index, batch) in texts.chunks(BATCH_SIZE).enumerate() {
+            if let Some(budget) = budget {
+                // Expect the next batch to take as long as the ones so far did on average
+                let next_batch = if batch_index == 0 { Duration::ZERO } else { start.elapsed() / batch_index as u32 };
+                if start.elapsed() + next_batch > budget {
+                    break;
+                }
+            }
+
+            let pairs: Vec<(String, String)> = batch.iter().map(|text| (query.to_string(), text.clone())).collect();
+            let encodings = self.tokenizer.encode_batch(pairs, true)
+                .map_err(|e| anyhow::anyhow!("{}", e))?;
+            let (values, shape, _) = run_padded(&self.session, &encodings, self.pad_id)?;
+            match shape[..] {
+                // Relevance logits, [pairs, 1], or [pairs, labels] with "relevant" last
+                [rows, labels] if rows == batch.len() && labels > 0 => {
+                    scores.extend(values.chunks_exact(labels).map(relevance));
+                }
+                ref shape => anyhow::bail!("Unexpected model output shape {:?}", shape),
+            }
+        }
+        Ok(scores)
+    }
+}
+
+/// Probability that a pair is relevant, so scores mean the same across models:
+/// the sigmoid of a single logit, or the softmax of the last of several labels
+fn relevance(logits: &[f32]) -> f32 {
+    match logits {
+        [logit] => 1.0 / (1.0 + (-logit).exp()),
+        _ => {
+            let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            let sum: f32 = logits.iter().map(|logit| (logit - max).exp()).sum();
+            (logits[logits.len() - 1] - max).exp() / sum
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relevance_is_a_probability() {
+        assert_eq!(relevance(&[0.0]), 0.5);
+        assert!((relevance(&[2.0]) - 0.880797).abs() < 1e-6);
+        // Two labels, "relevant" last
+        assert!((relevance(&[0.0, 2.0]) - 0.880797).abs() < 1e-6);
+        assert!((relevance(&[1000.0, -1000.0])).abs() < 1e-6);
+        assert!(relevance(&[-3.0, 1.0, 4.0]) > relevance(&[-3.0, 4.0, 1.0]));
+    }
+}
@@ -0,0 +1,124 @@
+/*
+    Embedding engines behind one trait. Loaded models are kept by model id
+    whatever engine runs them, so the bridge (and Dart) only ever names a model.
+*/
+use crate::api::embedding::EmbeddingRole;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Instant;
+
+pub mod cache;
+#[cfg(feature = "rustpotion")]
+pub mod potion;
+#[cfg(feature = "onnx")]
+pub mod onnx;
+#[cfg(feature = "onnx")]
+pub mod cross_encoder;
+
+pub trait EmbeddingBackend: Send + Sync {
+    /// Id the model was loaded as
+    fn model_id(&self) -> &str;
+
+    /// Length of the embeddings
+    fn dimensions(&self) -> usize;
+
+    /// Embeds `text` as `role`, with whatever prefix or query projection the
+    /// model configures for it
+    fn embed(&self, text: &str, role: EmbeddingRole) -> Result<Vec<f32>>;
+
+    fn embed_batch(&self, texts: Vec<String>, role: EmbeddingRole) -> Result<Vec<Vec<f32>>>;
+
+    /// Settings besides the model that change its embeddings, so the
+    /// embedding cache keeps what they give apart
+    fn settings(&self) -> String {
+        String::new()
+    }
+
+    /// Hash of the model's files and configuration, so the embedding cache
+    /// never serves vectors of a model that was replaced under the same id.
+    /// Computed when first asked for, as it may read every weight.
+    fn fingerprint(&self) -> Result<String>;
+
+    /// The model2vec model behind this backend, for what only static
+    /// embeddings can do (match explanations, corpus words, token weights)
+    #[cfg(feature = "rustpotion")]
+    fn potion(&self) -> Option<&potion::PotionBackend> {
+        None
+    }
+}
+
+// Loaded models by model id. Calls clone the Arc, so unloading or swapping a
+// model never pulls it away from an embedding call that is still running.
+static BACKENDS: OnceLock<RwLock<HashMap<String, Arc<dyn EmbeddingBackend>>>> = OnceLock::new();
+
+fn backends() -> &'static RwLock<HashMap<String, Arc<dyn EmbeddingBackend>>> {
+    BACKENDS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+pub fn get_backend(model_id: &str) -> Result<Arc<dyn EmbeddingBackend>> {
+    backends().read().unwrap()
+        .get(model_id)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Embedding model '{}' not initialized", model_id))
+}
+
+/// Loaded models, sorted by model id
+pub fn loaded_backends() -> Vec<Arc<dyn EmbeddingBackend>> {
+    let mut loaded: Vec<_> = backends().read().unwrap().values().cloned().collect();
+    loaded.sort_by(|a, b| a.model_id().cmp(b.model_id()));
+    loaded
+}
+
+/// Loads `model_id` unless it is loaded already, or in place of the loaded one
+/// with `replace`
+pub fn set_backend(model_id: &str, replace: bool, load: impl FnOnce() -> Result<Arc<dyn EmbeddingBackend>>) -> Result<bool> {
+    let start = Instant::now();
+
+    if !replace && backends().read().unwrap().contains_key(model_id) {
+        println!("Embedding model '{}' already initialized, keeping the loaded model", model_id);
+        return Ok(true);
+    }
+
+    // Load without holding the lock so other models keep embedding meanwhile
+    let backend = load()?;
+    let mut backends = backends().write().unwrap();
+    if replace || !backends.contains_key(model_id) {
+        backends.insert(model_id.to_string(), backend);
+    }
+    drop(backends);
+    // Cached embeddings were made by the model that was there before
+    if replace {
+        if let Some(cache) = cache::cache() {
+            cache.forget_model(model_id)?;
+        }
+    }
+
+    println!("Embedding model '{}' initialization took: {:?}", model_id, start.elapsed());
+    Ok(true)
+}
+
+/// Drops `model_id`, false if it wasn't loaded
+pub fn unload_backend(model_id: &str) -> bool {
+    backends().write().unwrap().remove(model_id).is_some()
+}
+
+/// Opens the model in `model_dir` with the engine its files are for:
+/// model.safetensors is a model2vec model run by RustPotion, model.onnx a
+/// sentence-transformer run by ONNX Runtime.
+pub fn open_backend(model_id: &str, model_dir: &Path) -> Result<Arc<dyn EmbeddingBackend>> {
+    if model_dir.join("model.safetensors").exists() {
+        #[cfg(feature = "rustpotion")]
+        return Ok(Arc::new(potion::PotionBackend::new(model_id, rustpotion::RustPotion::from_dir(model_dir)?)));
+        #[cfg(not(feature = "rustpotion"))]
+        anyhow::bail!("{:?} is a model2vec model, but this build has no rustpotion feature", model_dir);
+    }
+    if model_dir.join("model.onnx").exists() {
+        #[cfg(feature = "onnx")]
+        return Ok(Arc::new(onnx::OnnxBackend::from_dir(model_id, model_dir)?));
+        #[cfg(not(feature = "onnx"))]
+        anyhow::bail!("{:?} is an ONNX model, but this build has no onnx feature", model_dir);
+    }
+    anyhow::bail!("No model.safetensors or model.onnx in {:?}", model_dir)
+}
@@ -0,0 +1,326 @@
+use super::EmbeddingBackend;
+use crate::api::embedding::EmbeddingRole;
+use anyhow::Result;
+use ort::session::{builder::GraphOptimizationLevel, Session};
+use ort::value::Tensor;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tokenizers::{Encoding, Tokenizer, TruncationParams};
+
+// Longest input BERT-style sentence-transformers take
+const MAX_TOKENS: usize = 512;
+
+// Texts per inference run
+const BATCH_SIZE: usize = 50;
+
+// Path ONNX Runtime was loaded from. ort keeps the first library it's given
+// for the life of the process, so later calls can only check they agree.
+static RUNTIME_LIBRARY: Mutex<Option<String>> = Mutex::new(None);
+
+fn default_runtime_library() -> Result<String> {
+    // Bundled next to the executable in the Linux build
+    #[cfg(target_os = "linux")]
+    {
+        let exe_path = std::env::current_exe()?;
+        let lib_dir = exe_path.parent()
+            .ok_or_else(|| anyhow::anyhow!("Executable path {:?} has no parent directory", exe_path))?
+            .join("lib");
+        Ok(lib_dir.join("libonnxruntime.so").to_string_lossy().into_owned())
+    }
+    // Found by the dynamic linker among the APK's native libraries
+    #[cfg(target_os = "android")]
+    {
+        Ok("libonnxruntime.so".to_string())
+    }
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    {
+        Ok("libonnxruntime.dylib".to_string())
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Ok("onnxruntime.dll".to_string())
+    }
+    // Anything else has to say where it is with `init_runtime`
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "windows")))]
+    {
+        anyhow::bail!("No default ONNX Runtime library for this platform, load one with init_onnx_runtime")
+    }
+}
+
+// ort panics when the library is missing or older than it was built for, so
+// open it here first and turn either case into an error.
+fn check_runtime_library(library_path: &str) -> Result<()> {
+    let library = unsafe { libloading::Library::new(library_path) }
+        .map_err(|e| anyhow::anyhow!("Failed to load ONNX Runtime from {}: {}", library_path, e))?;
+    let version = unsafe {
+        let get_api_base = library.get::<unsafe extern "C" fn() -> *const ort::sys::OrtApiBase>(b"OrtGetApiBase")
+            .map_err(|e| anyhow::anyhow!("{} is not an ONNX Runtime library: {}", library_path, e))?;
+        let get_version_string = get_api_base().as_ref()
+            .and_then(|api_base| api_base.GetVersionString)
+            .ok_or_else(|| anyhow::anyhow!("{} has no ONNX Runtime version", library_path))?;
+        CStr::from_ptr(get_version_string()).to_string_lossy().into_owned()
+    };
+
+    let minor_version = version.split('.').nth(1).and_then(|minor| minor.parse::<u32>().ok()).unwrap_or(0);
+    if minor_version < ort::MINOR_VERSION {
+        anyhow::bail!("ONNX Runtime {} at {} is too old, 1.{}.x or newer is needed", version, library_path, ort::MINOR_VERSION);
+    }
+    println!("Found ONNX Runtime {} at {}", version, library_path);
+    Ok(())
+}
+
+/// Loads ONNX Runtime from `library_path`, or from where the app bundles it.
+/// Does nothing if it is loaded already, as long as the paths agree.
+pub fn init_runtime(library_path: Option<&str>) -> Result<()> {
+    let mut runtime_library = RUNTIME_LIBRARY.lock().unwrap();
+    if let Some(loaded_path) = runtime_library.as_deref() {
+        return match library_path {
+            Some(library_path) if library_path != loaded_path => {
+                anyhow::bail!("ONNX Runtime is already loaded from {}", loaded_path)
+            }
+            _ => Ok(()),
+        };
+    }
+
+    let library_path = match library_path {
+        Some(library_path) => library_path.to_string(),
+        None => default_runtime_library()?,
+    };
+    check_runtime_library(&library_path)?;
+    ort::init_from(&library_path)
+        .with_name("offline_engine")
+        .commit()?;
+    *runtime_library = Some(library_path);
+    Ok(())
+}
+
+/// Loads `model_file` for CPU inference, loading ONNX Runtime from its default
+/// path first if `init_runtime` wasn't called yet
+pub fn load_session(model_file: &Path) -> Result<Session> {
+    init_runtime(None)?;
+    Ok(Session::builder()?
+        .with_optimization_level(GraphOptimizationLevel::Level3)?
+        .with_intra_threads(4)?
+        .commit_from_file(model_file)?)
+}
+
+/// Makes `tokenizer` truncate to what the model takes, unless it already
+/// truncates, and returns the id to pad with
+pub fn prepare_tokenizer(tokenizer: &mut Cow<'static, Tokenizer>) -> Result<u32> {
+    if tokenizer.get_truncation().is_none() {
+        tokenizer.to_mut()
+            .with_truncation(Some(TruncationParams { max_length: MAX_TOKENS, ..Default::default() }))
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+    }
+    Ok(match tokenizer.get_padding() {
+        Some(padding) => padding.pad_id,
+        None => tokenizer.token_to_id("[PAD]").unwrap_or(0),
+    })
+}
+
+/// One inference run over `encodings`, padded to the longest of them. Returns
+/// the values and shape of the first output, and the attention mask used.
+pub fn run_padded(session: &Session, encodings: &[Encoding], pad_id: u32) -> Result<(Vec<f32>, Vec<usize>, Vec<i64>)> {
+    let batch_size = encodings.len();
+    let padded_length = encodings.iter().map(|encoding| encoding.len()).max().unwrap_or(0);
+    let padded = |values: fn(&Encoding) -> &[u32], pad: u32| -> Vec<i64> {
+        encodings.iter()
+            .flat_map(|encoding| {
+                let values = values(encoding);
+                values.iter().copied()
+                    .chain(std::iter::repeat_n(pad, padded_length - values.len()))
+                    .map(|x| x as i64)
+            })
+            .collect()
+    };
+    let attention_mask = padded(Encoding::get_attention_mask, 0);
+
+    let inputs = session.inputs.iter()
+        .map(|input| {
+            let values = match input.name.as_str() {
+                "input_ids" => padded(Encoding::get_ids, pad_id),
+                "attention_mask" => attention_mask.clone(),
+                "token_type_ids" => padded(Encoding::get_type_ids, 0),
+                name => anyhow::bail!("Unsupported model input '{}'", name),
+            };
+            Ok((input.name.as_str(), Tensor::from_array(([batch_size, padded_length], values))?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let outputs = session.run(inputs)?;
+    let output_name = session.outputs.first()
+        .ok_or_else(|| anyhow::anyhow!("No outputs available"))?
+        .name.as_str();
+    let output = outputs[output_name].try_extract_tensor::<f32>()?;
+    Ok((output.iter().copied().collect(), output.shape().to_vec(), attention_mask))
+}
+
+/// A sentence-transformer exported to ONNX, e.g. all-MiniLM-L6-v2 or bge-micro-v2
+pub struct OnnxBackend {
+    model_id: String,
+    session: Session,
+    tokenizer: Cow<'static, Tokenizer>,
+    pad_id: u32,
+    dimensions: usize,
+    // Put before queries / documents, e.g. "query: " and "passage: " for e5
+    query_prefix: Option<String>,
+    document_prefix: Option<String>,
+    model_file: PathBuf,
+    fingerprint: OnceLock<String>,
+}
+
+impl OnnxBackend {
+    /// Loads model.onnx and tokenizer.json from `model_dir`, and the query and
+    /// document prompts from config_sentence_transformers.json if it has one
+    pub fn from_dir(model_id: &str, model_dir: &Path) -> Result<Self> {
+        let tokenizer = Tokenizer::from_file(model_dir.join("tokenizer.json"))
+            .map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {}", e))?;
+        let mut backend = Self::new(model_id, &model_dir.join("model.onnx"), Cow::Owned(tokenizer))?;
+
+        let config_path = model_dir.join("config_sentence_transformers.json");
+        if config_path.exists() {
+            let config: serde_json::Value = serde_json::from_slice(&std::fs::read(&config_path)?)
+                .map_err(|e| anyhow::anyhow!("Failed to read {:?}: {}", config_path, e))?;
+            let prompt = |name: &str| config["prompts"][name].as_str().map(str::to_string);
+            backend.query_prefix = prompt("query");
+            backend.document_prefix = prompt("document").or_else(|| prompt("passage"));
+        }
+        Ok(backend)
+    }
+
+    pub fn new(model_id: &str, model_file: &Path, mut tokenizer: Cow<'static, Tokenizer>) -> Result<Self> {
+        let session = load_session(model_file)?;
+        let pad_id = prepare_tokenizer(&mut tokenizer)?;
+
+        let mut backend = OnnxBackend {
+            model_id: model_id.to_string(),
+            session,
+            tokenizer,
+            pad_id,
+            dimensions: 0,
+            query_prefix: None,
+            document_prefix: None,
+            model_file: model_file.to_path_buf(),
+            fingerprint: OnceLock::new(),
+        };
+        // The hidden size is usually left dynamic in the exported output shape
+        backend.dimensions = backend.embed("dimensions", EmbeddingRole::Document)?.len();
+        Ok(backend)
+    }
+
+    fn prefix(&self, role: EmbeddingRole) -> Option<&str> {
+        match role {
+            EmbeddingRole::Query => self.query_prefix.as_deref(),
+            EmbeddingRole::Document => self.document_prefix.as_deref(),
+        }
+    }
+
+    fn infer(&self, encodings: &[Encoding]) -> Result<Vec<Vec<f32>>> {
+        let (values, shape, attention_mask) = run_padded(&self.session, encodings, self.pad_id)?;
+
+        let embeddings = match shape[..] {
+            // Already pooled, [batch, dimensions]
+            [rows, dimensions] if rows == encodings.len() && dimensions > 0 => values.chunks_exact(dimensions)
+                .map(|embedding| normalized(embedding.to_vec()))
+                .collect(),
+            // Token embeddings, [batch, tokens, dimensions]
+            [rows, tokens, dimensions] if rows == encodings.len() && rows * tokens == attention_mask.len() && dimensions > 0 => values.chunks_exact(tokens * dimensions)
+                .zip(attention_mask.chunks_exact(tokens))
+                .map(|(token_embeddings, mask)| normalized(mean_pooling(token_embeddings, dimensions, mask)))
+                .collect(),
+            ref shape => anyhow::bail!("Unexpected model output shape {:?}", shape),
+        };
+        Ok(embeddings)
+    }
+}
+
+fn mean_pooling(token_embeddings: &[f32], dimensions: usize, attention_mask: &[i64]) -> Vec<f32> {
+    let mut mean_embedding = vec![0.0; dimensions];
+    let mut sum_mask = 0.0;
+
+    // Sum embeddings for tokens with attention mask = 1, padding has 0
+    for (token, &mask) in token_embeddings.chunks_exact(dimensions).zip(attention_mask) {
+        if mask == 1 {
+            for (sum, value) in mean_embedding.iter_mut().zip(token) {
+                *sum += value;
+            }
+            sum_mask += 1.0;
+        }
+    }
+
+    if sum_mask > 0.0 {
+        for val in mean_embedding.iter_mut() {
+            *val /= sum_mask;
+        }
+    }
+    mean_embedding
+}
+
+fn normalized(mut embedding: Vec<f32>) -> Vec<f32> {
+    let norm = embedding.iter().map(|&x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for val in embedding.iter_mut() {
+            *val /= norm;
+        }
+    }
+    embedding
+}
+
+impl EmbeddingBackend for OnnxBackend {
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn fingerprint(&self) -> Result<String> {
+        if let Some(fingerprint) = self.fingerprint.get() {
+            return Ok(fingerprint.clone());
+        }
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut std::fs::File::open(&self.model_file)?, &mut hasher)?;
+        hasher.update(self.tokenizer.to_string(false).map_err(|e| anyhow::anyhow!("{}", e))?);
+        hasher.update(format!("{:?}\0{:?}", self.query_prefix, self.document_prefix));
+        let fingerprint: String = hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect();
+        Ok(self.fingerprint.get_or_init(|| fingerprint).clone())
+    }
+
+    fn embed(&self, text: &str, role: EmbeddingRole) -> Result<Vec<f32>> {
+        let text = match self.prefix(role) {
+            Some(prefix) => Cow::Owned(format!("{}{}", prefix, text)),
+            None => Cow::Borrowed(text),
+        };
+        let encoding = self.tokenizer.encode(text.as_ref(), true)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        self.infer(&[encoding])?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("No embedding returned"))
+    }
+
+    fn embed_batch(&self, texts: Vec<String>, role: EmbeddingRole) -> Result<Vec<Vec<f32>>> {
+        let texts = match self.prefix(role) {
+            Some(prefix) => texts.into_iter().map(|text| format!("{}{}", prefix, text)).collect(),
+            None => texts,
+        };
+        let mut encodings = self.tokenizer.encode_batch(texts, true)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+        // Batch texts of similar length together so little of each run is padding
+        let mut order: Vec<usize> = (0..encodings.len()).collect();
+        order.sort_by_key(|&i| encodings[i].len());
+
+        let mut embeddings = vec![Vec::new(); encodings.len()];
+        for batch in order.chunks(BATCH_SIZE) {
+            let batch_encodings: Vec<Encoding> = batch.iter().map(|&i| std::mem::take(&mut encodings[i])).collect();
+            for (&i, embedding) in batch.iter().zip(self.infer(&batch_encodings)?) {
+                embeddings[i] = embedding;
+            }
+        }
+        Ok(embeddings)
+    }
+}
@@ -0,0 +1,73 @@
+use super::EmbeddingBackend;
+use crate::api::embedding::EmbeddingRole;
+use anyhow::Result;
+use rustpotion::{CorpusWords, LongText, Role, RustPotion};
+use std::sync::{Arc, OnceLock, RwLock};
+
+// How texts are cut before embedding until `set_embedding_token_budget` says
+// otherwise. A 512 token budget is close to the 2500 characters texts used to
+// be truncated to.
+const DEFAULT_LONG_TEXT: LongText = LongText::Truncate { max_tokens: 512 };
+
+pub struct PotionBackend {
+    model_id: String,
+    pub potion: Arc<RustPotion>,
+    // Words of the indexed corpus for `nearest_corpus_words`. Kept with the
+    // model, since the vectors come from its embedding table.
+    pub corpus_words: RwLock<Option<Arc<CorpusWords>>>,
+    // How this model cuts long texts. Kept per model, since it changes what
+    // the model's embeddings (and its cached ones) are.
+    pub long_text: RwLock<LongText>,
+    fingerprint: OnceLock<String>,
+}
+
+impl PotionBackend {
+    pub fn new(model_id: &str, potion: RustPotion) -> Self {
+        PotionBackend {
+            model_id: model_id.to_string(),
+            potion: Arc::new(potion),
+            corpus_words: RwLock::new(None),
+            long_text: RwLock::new(DEFAULT_LONG_TEXT),
+            fingerprint: OnceLock::new(),
+        }
+    }
+}
+
+fn potion_role(role: EmbeddingRole) -> Role {
+    match role {
+        EmbeddingRole::Query => Role::Query,
+        EmbeddingRole::Document => Role::Document,
+    }
+}
+
+impl EmbeddingBackend for PotionBackend {
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    fn dimensions(&self) -> usize {
+        self.potion.dimensions()
+    }
+
+    fn embed(&self, text: &str, role: EmbeddingRole) -> Result<Vec<f32>> {
+        let long_text = *self.long_text.read().unwrap();
+        Ok(self.potion.try_encode_as(text, potion_role(role), long_text)?.0)
+    }
+
+    fn embed_batch(&self, texts: Vec<String>, role: EmbeddingRole) -> Result<Vec<Vec<f32>>> {
+        let long_text = *self.long_text.read().unwrap();
+        Ok(self.potion.try_encode_many_as(texts, potion_role(role), long_text)?)
+    }
+
+    fn settings(&self) -> String {
+        format!("{:?}", *self.long_text.read().unwrap())
+    }
+
+    fn fingerprint(&self) -> Result<String> {
+        Ok(self.fingerprint.get_or_init(|| self.potion.fingerprint()).clone())
+    }
+
+    fn potion(&self) -> Option<&PotionBackend> {
+        Some(self)
+    }
+}
@@ -1,3 +1,4 @@
 pub mod api;
+mod backends;
 mod frb_generated;
 pub use api::*;
\ No newline at end of file